@@ -0,0 +1,31 @@
+#![feature(test)]
+
+extern crate test;
+
+#[cfg(test)]
+mod tests {
+    use swisslos_crawler::{evaluate_batch, LottoDraw};
+    use test::Bencher;
+
+    #[bench]
+    fn bench_evaluate_batch(b: &mut Bencher) {
+        let draw = LottoDraw {
+            numbers: [5, 19, 28, 36, 38, 41],
+            lucky: 5,
+            replay: 8,
+            ..LottoDraw::default()
+        };
+        let tickets = (0..10_000u32)
+            .map(|index| {
+                let base = (index % 37) as u8;
+                let ticket = [base + 1, base + 2, base + 3, base + 4, base + 5, base + 6];
+
+                (ticket, (index % 6) as u8 + 1)
+            })
+            .collect::<Vec<_>>();
+
+        b.iter(|| {
+            evaluate_batch(&draw, &tickets).unwrap();
+        });
+    }
+}
@@ -1,5 +1,5 @@
 use chrono::{Datelike, Weekday};
-use swisslos_crawler::SwissLottoClient;
+use swisslos_crawler::{NaiveDate, SwissLottoClient};
 
 #[tokio::main]
 async fn main() {
@@ -8,7 +8,6 @@ async fn main() {
     println!("{:?}", client.get_latest_draw().await);
 
     let today = chrono::offset::Local::today();
-    let mon =
-        chrono::NaiveDate::from_isoywd(today.year(), today.iso_week().week() - 1, Weekday::Thu);
+    let mon = NaiveDate::from_isoywd(today.year(), today.iso_week().week() - 1, Weekday::Thu);
     println!("{:?}", client.get_previous_draw(mon).await);
 }
@@ -0,0 +1,88 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Utc};
+
+/// Source of "today" for the client, so date-dependent behaviour can be pinned.
+pub trait Clock: Send + Sync {
+    fn now_date(&self) -> NaiveDate;
+}
+
+/// Today in Europe/Zurich, where the draws take place.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_date(&self) -> NaiveDate {
+        zurich_date(Utc::now().naive_utc())
+    }
+}
+
+/// The Zurich date at `utc`: CEST (UTC+2) from 01:00 UTC on the last Sunday of
+/// March until 01:00 UTC on the last Sunday of October, CET (UTC+1) otherwise.
+fn zurich_date(utc: NaiveDateTime) -> NaiveDate {
+    let year = utc.date().year();
+    let summer_time = clock_change(year, 3) <= utc && utc < clock_change(year, 10);
+    let offset = if summer_time { 2 } else { 1 };
+
+    (utc + Duration::hours(offset)).date()
+}
+
+/// 01:00 UTC on the last Sunday of `month`, which must have 31 days.
+fn clock_change(year: i32, month: u32) -> NaiveDateTime {
+    let last_day = NaiveDate::from_ymd_opt(year, month, 31).expect("month has 31 days");
+    let last_sunday =
+        last_day - Duration::days(i64::from(last_day.weekday().num_days_from_sunday()));
+
+    last_sunday.and_hms_opt(1, 0, 0).expect("valid time")
+}
+
+/// A clock that always reports the same date.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub NaiveDate);
+
+impl Clock for FixedClock {
+    fn now_date(&self) -> NaiveDate {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        date(year, month, day).and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn clock_changes_on_the_last_sundays() {
+        assert_eq!(clock_change(2022, 3), utc(2022, 3, 27, 1, 0));
+        assert_eq!(clock_change(2022, 10), utc(2022, 10, 30, 1, 0));
+        // the 31st itself is a Sunday
+        assert_eq!(clock_change(2024, 3), utc(2024, 3, 31, 1, 0));
+    }
+
+    #[test]
+    fn zurich_date_in_winter_is_one_hour_ahead() {
+        assert_eq!(zurich_date(utc(2022, 1, 10, 22, 59)), date(2022, 1, 10));
+        assert_eq!(zurich_date(utc(2022, 1, 10, 23, 0)), date(2022, 1, 11));
+        assert_eq!(zurich_date(utc(2022, 12, 31, 23, 0)), date(2023, 1, 1));
+    }
+
+    #[test]
+    fn zurich_date_in_summer_is_two_hours_ahead() {
+        assert_eq!(zurich_date(utc(2022, 7, 10, 21, 59)), date(2022, 7, 10));
+        assert_eq!(zurich_date(utc(2022, 7, 10, 22, 0)), date(2022, 7, 11));
+    }
+
+    #[test]
+    fn zurich_date_around_the_clock_changes() {
+        // CET until 01:00 UTC on 27.03.2022, CEST from then on
+        assert_eq!(zurich_date(utc(2022, 3, 26, 22, 59)), date(2022, 3, 26));
+        assert_eq!(zurich_date(utc(2022, 3, 26, 23, 0)), date(2022, 3, 27));
+        assert_eq!(zurich_date(utc(2022, 3, 27, 22, 0)), date(2022, 3, 28));
+        // CEST until 01:00 UTC on 30.10.2022, CET from then on
+        assert_eq!(zurich_date(utc(2022, 10, 29, 22, 0)), date(2022, 10, 30));
+        assert_eq!(zurich_date(utc(2022, 10, 30, 22, 59)), date(2022, 10, 30));
+        assert_eq!(zurich_date(utc(2022, 10, 30, 23, 0)), date(2022, 10, 31));
+    }
+}
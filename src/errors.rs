@@ -1,17 +1,110 @@
-use chrono::ParseError as ChronoParseError;
+use chrono::{NaiveDate, ParseError as ChronoParseError};
 use reqwest::Error as ReqwestError;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Error as SerdeJsonError;
+use std::io::Error as IoError;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum Errors {
     ReqwestClientError(ReqwestError),
     ParserError,
     SuppliedDateHasNoDraw,
+    ConsentRequired,
     UnexpectedParsingError(String, String),
     DateParsingError(ChronoParseError),
+    DeadlineExceeded,
+    Timeout(ReqwestError),
+    RateLimited(Duration),
+    RetryBudgetExhausted,
+    InvalidNumber(u8),
+    DuplicateNumber(u8),
+    InvalidLuckyNumber(u8),
+    InvalidReplayNumber(u8),
+    InvalidArgument(String),
+    InvalidDateRange(NaiveDate, NaiveDate),
+    FutureDrawDate(NaiveDate),
+    SerializationError(SerdeJsonError),
+    IoError(IoError),
+}
+
+impl Errors {
+    /// Name of the variant, e.g. `"SuppliedDateHasNoDraw"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ReqwestClientError(_) => "ReqwestClientError",
+            Self::ParserError => "ParserError",
+            Self::SuppliedDateHasNoDraw => "SuppliedDateHasNoDraw",
+            Self::ConsentRequired => "ConsentRequired",
+            Self::UnexpectedParsingError(_, _) => "UnexpectedParsingError",
+            Self::DateParsingError(_) => "DateParsingError",
+            Self::DeadlineExceeded => "DeadlineExceeded",
+            Self::Timeout(_) => "Timeout",
+            Self::RateLimited(_) => "RateLimited",
+            Self::RetryBudgetExhausted => "RetryBudgetExhausted",
+            Self::InvalidNumber(_) => "InvalidNumber",
+            Self::DuplicateNumber(_) => "DuplicateNumber",
+            Self::InvalidLuckyNumber(_) => "InvalidLuckyNumber",
+            Self::InvalidReplayNumber(_) => "InvalidReplayNumber",
+            Self::InvalidArgument(_) => "InvalidArgument",
+            Self::InvalidDateRange(_, _) => "InvalidDateRange",
+            Self::FutureDrawDate(_) => "FutureDrawDate",
+            Self::SerializationError(_) => "SerializationError",
+            Self::IoError(_) => "IoError",
+        }
+    }
+}
+
+/// Serializes as `{ "type": <kind>, ... }`. Inner errors are rendered as a
+/// `message` string, HTTP errors also carry their `status`, and raw HTML is
+/// omitted.
+impl Serialize for Errors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", self.kind())?;
+
+        match self {
+            Self::ReqwestClientError(e) => {
+                if let Some(status) = e.status() {
+                    map.serialize_entry("status", &status.as_u16())?;
+                }
+                map.serialize_entry("message", &e.to_string())?;
+            }
+            Self::Timeout(e) => map.serialize_entry("message", &e.to_string())?,
+            Self::UnexpectedParsingError(message, _) => map.serialize_entry("message", message)?,
+            Self::DateParsingError(e) => map.serialize_entry("message", &e.to_string())?,
+            Self::RateLimited(wait) => {
+                map.serialize_entry("retry_after_secs", &wait.as_secs_f64())?
+            }
+            Self::InvalidNumber(value)
+            | Self::DuplicateNumber(value)
+            | Self::InvalidLuckyNumber(value)
+            | Self::InvalidReplayNumber(value) => map.serialize_entry("value", value)?,
+            Self::InvalidArgument(message) => map.serialize_entry("message", message)?,
+            Self::InvalidDateRange(start, end) => {
+                map.serialize_entry("start", start)?;
+                map.serialize_entry("end", end)?;
+            }
+            Self::FutureDrawDate(date) => map.serialize_entry("date", date)?,
+            Self::SerializationError(e) => map.serialize_entry("message", &e.to_string())?,
+            Self::IoError(e) => map.serialize_entry("message", &e.to_string())?,
+            Self::ParserError
+            | Self::SuppliedDateHasNoDraw
+            | Self::ConsentRequired
+            | Self::DeadlineExceeded
+            | Self::RetryBudgetExhausted => {}
+        }
+
+        map.end()
+    }
 }
 
 impl From<ReqwestError> for Errors {
     fn from(e: ReqwestError) -> Self {
+        if e.is_timeout() {
+            return Self::Timeout(e);
+        }
+
         Self::ReqwestClientError(e)
     }
 }
@@ -20,4 +113,68 @@ impl From<ChronoParseError> for Errors {
     fn from(e: ChronoParseError) -> Self {
         Self::DateParsingError(e)
     }
-}
\ No newline at end of file
+}
+
+impl From<SerdeJsonError> for Errors {
+    fn from(e: SerdeJsonError) -> Self {
+        Self::SerializationError(e)
+    }
+}
+
+impl From<IoError> for Errors {
+    fn from(e: IoError) -> Self {
+        Self::IoError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_tagged_variants() {
+        assert_eq!(
+            serde_json::to_value(Errors::SuppliedDateHasNoDraw).unwrap(),
+            json!({ "type": "SuppliedDateHasNoDraw" })
+        );
+        assert_eq!(
+            serde_json::to_value(Errors::UnexpectedParsingError(
+                "Expected 6 normal numbers in html response, found 5".to_string(),
+                "<html></html>".to_string(),
+            ))
+            .unwrap(),
+            json!({
+                "type": "UnexpectedParsingError",
+                "message": "Expected 6 normal numbers in html response, found 5",
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_variant_fields() {
+        assert_eq!(
+            serde_json::to_value(Errors::RateLimited(Duration::from_millis(1500))).unwrap(),
+            json!({ "type": "RateLimited", "retry_after_secs": 1.5 })
+        );
+        assert_eq!(
+            serde_json::to_value(Errors::InvalidNumber(43)).unwrap(),
+            json!({ "type": "InvalidNumber", "value": 43 })
+        );
+        assert_eq!(
+            serde_json::to_value(Errors::InvalidDateRange(
+                NaiveDate::from_ymd_opt(2022, 3, 30).unwrap(),
+                NaiveDate::from_ymd_opt(2022, 3, 16).unwrap(),
+            ))
+            .unwrap(),
+            json!({ "type": "InvalidDateRange", "start": "2022-03-30", "end": "2022-03-16" })
+        );
+        assert_eq!(
+            serde_json::to_value(Errors::FutureDrawDate(
+                NaiveDate::from_ymd_opt(2022, 3, 30).unwrap()
+            ))
+            .unwrap(),
+            json!({ "type": "FutureDrawDate", "date": "2022-03-30" })
+        );
+    }
+}
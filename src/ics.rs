@@ -0,0 +1,64 @@
+use super::LottoDraw;
+
+/// Renders the draws as an iCalendar document with one all-day event per draw.
+pub fn draws_to_ics(draws: &[LottoDraw]) -> String {
+    let mut ics =
+        String::from("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//swisslos-crawler//EN\r\n");
+
+    for draw in draws {
+        let date = draw.date.format("%Y%m%d");
+        let numbers = draw
+            .numbers
+            .iter()
+            .map(|number| number.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        ics.push_str("BEGIN:VEVENT\r\n");
+        ics.push_str(&format!("UID:swisslotto-{}@swisslos-crawler\r\n", date));
+        ics.push_str(&format!("DTSTAMP:{}T000000Z\r\n", date));
+        ics.push_str(&format!("DTSTART;VALUE=DATE:{}\r\n", date));
+        ics.push_str("SUMMARY:Swiss Lotto draw\r\n");
+        ics.push_str(&format!(
+            "DESCRIPTION:Numbers {} / Lucky number {} / Replay {}\r\n",
+            numbers, draw.lucky, draw.replay
+        ));
+        ics.push_str("END:VEVENT\r\n");
+    }
+
+    ics.push_str("END:VCALENDAR\r\n");
+
+    ics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    #[test]
+    fn one_event_per_draw() {
+        let draws = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8),
+        ];
+
+        let ics = draws_to_ics(&draws);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT\r\n").count(), 2);
+        assert!(
+            ics.contains("DESCRIPTION:Numbers 5 19 28 36 38 41 / Lucky number 5 / Replay 8\r\n")
+        );
+        assert!(ics.contains("DTSTART;VALUE=DATE:20220326\r\n"));
+    }
+
+    #[test]
+    fn empty_history_is_an_empty_calendar() {
+        assert_eq!(
+            draws_to_ics(&[]),
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//swisslos-crawler//EN\r\nEND:VCALENDAR\r\n"
+        );
+    }
+}
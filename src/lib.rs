@@ -4,12 +4,51 @@
 #[macro_use]
 extern crate lazy_static;
 
-mod swiss_lotto;
+mod clock;
 mod errors;
+#[cfg(feature = "ics")]
+mod ics;
+mod schedule;
+mod stats;
+mod swiss_lotto;
+#[cfg(test)]
+mod test_support;
+mod ticket;
 
 #[doc(hidden)]
-pub use swiss_lotto::*;
+pub use clock::*;
 
 #[doc(hidden)]
 pub use errors::*;
 
+#[cfg(feature = "ics")]
+#[doc(hidden)]
+pub use ics::*;
+
+#[doc(hidden)]
+pub use schedule::*;
+
+#[doc(hidden)]
+pub use stats::*;
+
+#[doc(hidden)]
+pub use swiss_lotto::*;
+
+#[doc(hidden)]
+pub use ticket::*;
+
+/// Re-exported so callers use the same chrono version as the crate's API.
+///
+/// ```no_run
+/// use swisslos_crawler::{NaiveDate, SwissLottoClient};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), swisslos_crawler::Errors> {
+/// let date = NaiveDate::from_ymd_opt(2022, 3, 30).expect("valid date");
+/// let draw = SwissLottoClient::default().get_draw_of_date(date).await?;
+///
+/// println!("{:?}", draw.numbers);
+/// # Ok(())
+/// # }
+/// ```
+pub use chrono::{DateTime, NaiveDate, Utc, Weekday};
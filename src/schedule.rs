@@ -0,0 +1,165 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use super::Errors;
+
+/// Swiss Lotto is drawn every Wednesday and Saturday.
+pub const DRAW_WEEKDAYS: [Weekday; 2] = [Weekday::Wed, Weekday::Sat];
+
+/// An inclusive date range whose start is never after its end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(try_from = "UncheckedDrawDateRange")]
+pub struct DrawDateRange {
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+#[derive(Deserialize)]
+struct UncheckedDrawDateRange {
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+impl TryFrom<UncheckedDrawDateRange> for DrawDateRange {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedDrawDateRange) -> Result<Self, Self::Error> {
+        Self::new(unchecked.start, unchecked.end)
+            .map_err(|e| format!("invalid date range: {:?}", e))
+    }
+}
+
+impl DrawDateRange {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Result<Self, Errors> {
+        if start > end {
+            return Err(Errors::InvalidDateRange(start, end));
+        }
+
+        Ok(Self { start, end })
+    }
+
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    pub fn end(&self) -> NaiveDate {
+        self.end
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+pub fn is_draw_day(date: NaiveDate) -> bool {
+    DRAW_WEEKDAYS.contains(&date.weekday())
+}
+
+/// Scheduled draw dates within `range`.
+pub fn draw_dates_between(range: &DrawDateRange) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    let mut current = Some(range.start);
+
+    while let Some(date) = current.filter(|date| *date <= range.end) {
+        if is_draw_day(date) {
+            dates.push(date);
+        }
+
+        current = date.succ_opt();
+    }
+
+    dates
+}
+
+/// Number of scheduled draws in `date`'s year up to and including `date`.
+pub fn draw_ordinal(date: NaiveDate) -> u32 {
+    let range = DrawDateRange {
+        start: date.with_ordinal(1).unwrap(),
+        end: date,
+    };
+
+    draw_dates_between(&range).len() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    #[test]
+    fn new_accepts_valid_range() {
+        let range = DrawDateRange::new(date(2022, 3, 16), date(2022, 3, 30)).unwrap();
+
+        assert_eq!(range.start(), date(2022, 3, 16));
+        assert_eq!(range.end(), date(2022, 3, 30));
+        assert!(range.contains(date(2022, 3, 23)));
+        assert!(!range.contains(date(2022, 4, 2)));
+        assert!(DrawDateRange::new(date(2022, 3, 30), date(2022, 3, 30)).is_ok());
+    }
+
+    #[test]
+    fn new_rejects_reversed_range() {
+        let result = DrawDateRange::new(date(2022, 3, 30), date(2022, 3, 16));
+
+        assert!(matches!(
+            result,
+            Err(Errors::InvalidDateRange(start, end))
+                if start == date(2022, 3, 30) && end == date(2022, 3, 16)
+        ));
+    }
+
+    #[test]
+    fn deserialize_validates_range() {
+        let range =
+            serde_json::from_str::<DrawDateRange>(r#"{"start":"2022-03-16","end":"2022-03-30"}"#)
+                .unwrap();
+        let reversed =
+            serde_json::from_str::<DrawDateRange>(r#"{"start":"2022-03-30","end":"2022-03-16"}"#);
+
+        assert_eq!(
+            range,
+            DrawDateRange::new(date(2022, 3, 16), date(2022, 3, 30)).unwrap()
+        );
+        assert!(reversed.is_err());
+        assert_eq!(
+            serde_json::to_string(&range).unwrap(),
+            r#"{"start":"2022-03-16","end":"2022-03-30"}"#
+        );
+    }
+
+    #[test]
+    fn draw_dates_between_keeps_wednesdays_and_saturdays() {
+        assert_eq!(
+            draw_dates_between(&range(date(2022, 3, 21), date(2022, 3, 30))),
+            [date(2022, 3, 23), date(2022, 3, 26), date(2022, 3, 30)]
+        );
+        assert!(is_draw_day(date(2022, 3, 30)));
+        assert!(!is_draw_day(date(2022, 3, 31)));
+    }
+
+    #[test]
+    fn draw_dates_between_edge_cases() {
+        assert_eq!(
+            draw_dates_between(&range(date(2022, 3, 30), date(2022, 3, 30))),
+            [date(2022, 3, 30)]
+        );
+        assert!(draw_dates_between(&range(date(2022, 3, 31), date(2022, 4, 1))).is_empty());
+    }
+
+    #[test]
+    fn draw_ordinal_mid_year() {
+        // Wednesday; 2022 starts on a Saturday draw day
+        assert_eq!(draw_ordinal(date(2022, 6, 29)), 52);
+        // Friday after it
+        assert_eq!(draw_ordinal(date(2022, 7, 1)), 52);
+    }
+
+    #[test]
+    fn draw_ordinal_at_year_boundaries() {
+        assert_eq!(draw_ordinal(date(2022, 1, 1)), 1);
+        // 2021 starts on a Friday, before its first draw
+        assert_eq!(draw_ordinal(date(2021, 1, 1)), 0);
+        // 2022 has 53 Saturdays and 52 Wednesdays
+        assert_eq!(draw_ordinal(date(2022, 12, 31)), 105);
+    }
+}
@@ -0,0 +1,1598 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+#[cfg(feature = "rand")]
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::{
+    draw_dates_between, validate_lucky_number, DrawDateRange, Errors, LottoDraw, MAX_NUMBER,
+    MAX_REPLAY_NUMBER,
+};
+
+/// Counts how often each unordered pair of main numbers was drawn together.
+///
+/// Pairs are keyed with the smaller number first.
+pub fn pair_cooccurrence(draws: &[LottoDraw]) -> HashMap<(u8, u8), u32> {
+    let mut pairs = HashMap::new();
+
+    for draw in draws {
+        let mut numbers = draw.numbers;
+        numbers.sort_unstable();
+
+        for (index, first) in numbers.iter().enumerate() {
+            for second in &numbers[index + 1..] {
+                *pairs.entry((*first, *second)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    pairs
+}
+
+/// Scores how overdue each main number is, with `draws` ordered newest-first.
+///
+/// The score is `draws_since_last_seen / expected_gap`, where
+/// `expected_gap = total_draws / expected_appearances` and
+/// `expected_appearances = total_draws * 6 / 42`. A number never seen in
+/// `draws` has `draws_since_last_seen = total_draws`. Empty input yields an
+/// empty map.
+pub fn overdue_scores(draws: &[LottoDraw]) -> BTreeMap<u8, f64> {
+    let mut scores = BTreeMap::new();
+
+    if draws.is_empty() {
+        return scores;
+    }
+
+    let total_draws = draws.len() as f64;
+    let expected_appearances = total_draws * 6.0 / f64::from(MAX_NUMBER);
+    let expected_gap = total_draws / expected_appearances;
+
+    for number in 1..=MAX_NUMBER {
+        let draws_since_last_seen = draws
+            .iter()
+            .position(|draw| draw.numbers.contains(&number))
+            .unwrap_or(draws.len());
+
+        scores.insert(number, draws_since_last_seen as f64 / expected_gap);
+    }
+
+    scores
+}
+
+/// Longest run of consecutive draws each main number was absent from.
+///
+/// `draws` must be ordered by date; the direction does not matter.
+pub fn longest_absence(draws: &[LottoDraw]) -> BTreeMap<u8, u32> {
+    let mut longest = BTreeMap::new();
+
+    for number in 1..=MAX_NUMBER {
+        let mut current = 0;
+        let mut max = 0;
+
+        for draw in draws {
+            if draw.numbers.contains(&number) {
+                current = 0;
+            } else {
+                current += 1;
+                max = max.max(current);
+            }
+        }
+
+        longest.insert(number, max);
+    }
+
+    longest
+}
+
+/// Longest run of consecutive draws each main number appeared in.
+///
+/// `draws` must be ordered by date; the direction does not matter.
+pub fn longest_presence(draws: &[LottoDraw]) -> BTreeMap<u8, u32> {
+    let mut longest = BTreeMap::new();
+
+    for number in 1..=MAX_NUMBER {
+        let mut current = 0;
+        let mut max = 0;
+
+        for draw in draws {
+            if draw.numbers.contains(&number) {
+                current += 1;
+                max = max.max(current);
+            } else {
+                current = 0;
+            }
+        }
+
+        longest.insert(number, max);
+    }
+
+    longest
+}
+
+/// Keeps the draws that took place on `weekday`, in their original order.
+pub fn filter_by_weekday(draws: &[LottoDraw], weekday: Weekday) -> Vec<&LottoDraw> {
+    draws
+        .iter()
+        .filter(|draw| draw.date.weekday() == weekday)
+        .collect()
+}
+
+/// Histogram of main-number sums, keyed by each bucket's lower bound.
+pub fn sum_histogram(draws: &[LottoDraw], bucket_size: u16) -> Result<BTreeMap<u16, u32>, Errors> {
+    if bucket_size == 0 {
+        return Err(Errors::InvalidArgument(
+            "Expected a bucket size greater than 0".to_string(),
+        ));
+    }
+
+    let mut histogram = BTreeMap::new();
+
+    for draw in draws {
+        let bucket = draw.main_sum() / bucket_size * bucket_size;
+        *histogram.entry(bucket).or_insert(0) += 1;
+    }
+
+    Ok(histogram)
+}
+
+fn sum_counts(draws: &[LottoDraw]) -> BTreeMap<u16, u32> {
+    let mut counts = BTreeMap::new();
+
+    for draw in draws {
+        *counts.entry(draw.main_sum()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+/// Most frequent main-number sum and its count, ties going to the lowest sum;
+/// `None` for no draws.
+pub fn sum_mode(draws: &[LottoDraw]) -> Option<(u16, u32)> {
+    // max_by_key keeps the last maximum, so walk from the highest sum down
+    sum_counts(draws)
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+}
+
+/// Least frequent main-number sum that occurred and its count, ties going to
+/// the lowest sum; `None` for no draws.
+pub fn sum_least(draws: &[LottoDraw]) -> Option<(u16, u32)> {
+    sum_counts(draws)
+        .into_iter()
+        .min_by_key(|(_, count)| *count)
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HistoryAudit {
+    /// Scheduled draw dates in the range with no matching draw.
+    pub missing: Vec<NaiveDate>,
+    /// Dates of draws that fall outside the range.
+    pub out_of_range: Vec<NaiveDate>,
+}
+
+/// Compares `draws` against the draw schedule within `range`.
+pub fn audit_history(draws: &[LottoDraw], range: &DrawDateRange) -> HistoryAudit {
+    let missing = draw_dates_between(range)
+        .into_iter()
+        .filter(|date| !draws.iter().any(|draw| draw.date == *date))
+        .collect();
+
+    let out_of_range = draws
+        .iter()
+        .map(|draw| draw.date)
+        .filter(|date| !range.contains(*date))
+        .collect();
+
+    HistoryAudit {
+        missing,
+        out_of_range,
+    }
+}
+
+/// Main numbers drawn in the `k` most recent draws, with `draws` ordered newest-first.
+///
+/// `k` is clamped to the number of draws.
+pub fn recent_numbers(draws: &[LottoDraw], k: usize) -> BTreeSet<u8> {
+    draws[..k.min(draws.len())]
+        .iter()
+        .flat_map(|draw| draw.numbers)
+        .collect()
+}
+
+/// Counts how often each sorted triplet of main numbers was drawn together.
+///
+/// Only triplets that actually occurred are stored, but each draw contributes
+/// C(6, 3) = 20 triplets, so long histories can approach all C(42, 3) = 11'480
+/// keys.
+pub fn triplet_cooccurrence(draws: &[LottoDraw]) -> HashMap<(u8, u8, u8), u32> {
+    let mut triplets = HashMap::new();
+
+    for draw in draws {
+        let mut numbers = draw.numbers;
+        numbers.sort_unstable();
+
+        for (first_index, first) in numbers.iter().enumerate() {
+            for (second_index, second) in numbers.iter().enumerate().skip(first_index + 1) {
+                for third in &numbers[second_index + 1..] {
+                    *triplets.entry((*first, *second, *third)).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    triplets
+}
+
+/// How often each main number from 1 to 42 was drawn, including zero counts.
+pub fn number_frequencies(draws: &[LottoDraw]) -> BTreeMap<u8, u32> {
+    let mut frequencies: BTreeMap<u8, u32> = (1..=MAX_NUMBER).map(|number| (number, 0)).collect();
+
+    for number in draws.iter().flat_map(|draw| draw.numbers) {
+        if let Some(frequency) = frequencies.get_mut(&number) {
+            *frequency += 1;
+        }
+    }
+
+    frequencies
+}
+
+/// Suggests six distinct main numbers, weighted by how often each was drawn.
+///
+/// Lottery draws are independent: past frequencies have no predictive power
+/// and this is for entertainment only. The result is sorted and deterministic
+/// for the same `history` and `seed`.
+#[cfg(feature = "rand")]
+pub fn suggest_numbers(history: &[LottoDraw], seed: u64) -> [u8; 6] {
+    let candidates = number_frequencies(history).into_iter().collect::<Vec<_>>();
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let chosen = candidates
+        .choose_multiple_weighted(&mut rng, 6, |(_, frequency)| f64::from(*frequency + 1))
+        .unwrap();
+
+    let mut numbers = [0; 6];
+    for (slot, (number, _)) in numbers.iter_mut().zip(chosen) {
+        *slot = *number;
+    }
+    numbers.sort_unstable();
+
+    numbers
+}
+
+/// Main-number frequencies per `(year, month)`, for calendar heatmaps.
+pub fn monthly_frequencies(draws: &[LottoDraw]) -> BTreeMap<(i32, u32), BTreeMap<u8, u32>> {
+    let mut months = BTreeMap::new();
+
+    for draw in draws {
+        let frequencies = months
+            .entry((draw.date.year(), draw.date.month()))
+            .or_insert_with(|| number_frequencies(&[]));
+
+        for number in draw.numbers {
+            if let Some(frequency) = frequencies.get_mut(&number) {
+                *frequency += 1;
+            }
+        }
+    }
+
+    months
+}
+
+/// Main numbers from 1 to 42 that appear in none of `draws`.
+pub fn never_drawn(draws: &[LottoDraw]) -> BTreeSet<u8> {
+    number_frequencies(draws)
+        .into_iter()
+        .filter(|(_, frequency)| *frequency == 0)
+        .map(|(number, _)| number)
+        .collect()
+}
+
+/// Main-number frequencies over each sliding window of `window` draws.
+///
+/// `draws` must be sorted ascending by date; each window is keyed by the date
+/// of its most recent draw.
+pub fn rolling_frequencies(
+    draws: &[LottoDraw],
+    window: usize,
+) -> Result<Vec<(NaiveDate, BTreeMap<u8, u32>)>, Errors> {
+    if window == 0 || window > draws.len() {
+        return Err(Errors::InvalidArgument(format!(
+            "Expected a window between 1 and {}, found {}",
+            draws.len(),
+            window
+        )));
+    }
+
+    Ok(draws
+        .windows(window)
+        .map(|slice| (slice[window - 1].date, number_frequencies(slice)))
+        .collect())
+}
+
+/// The `k` numbers drawn most often together with `number`, most frequent first.
+///
+/// Ties are ordered by the lower number first.
+pub fn companions(draws: &[LottoDraw], number: u8, k: usize) -> Result<Vec<(u8, u32)>, Errors> {
+    if number == 0 || number > MAX_NUMBER {
+        return Err(Errors::InvalidNumber(number));
+    }
+
+    let mut counts = BTreeMap::new();
+
+    for draw in draws.iter().filter(|draw| draw.numbers.contains(&number)) {
+        for companion in draw.numbers {
+            if companion != number {
+                *counts.entry(companion).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut companions = counts.into_iter().collect::<Vec<_>>();
+    companions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    companions.truncate(k);
+
+    Ok(companions)
+}
+
+/// Gini coefficient of the 42 main-number frequencies.
+///
+/// Computed as `sum(|x_i - x_j|) / (2 * n^2 * mean)` over all pairs of
+/// frequencies: 0 for perfectly even draws, approaching 1 when few numbers
+/// dominate. Returns 0 for empty input.
+pub fn frequency_gini(draws: &[LottoDraw]) -> f64 {
+    let frequencies = number_frequencies(draws)
+        .into_values()
+        .map(f64::from)
+        .collect::<Vec<_>>();
+
+    let count = frequencies.len() as f64;
+    let mean = frequencies.iter().sum::<f64>() / count;
+
+    if mean == 0.0 {
+        return 0.0;
+    }
+
+    let absolute_differences = frequencies
+        .iter()
+        .flat_map(|a| frequencies.iter().map(move |b| (a - b).abs()))
+        .sum::<f64>();
+
+    absolute_differences / (2.0 * count * count * mean)
+}
+
+/// Per main number, its appearance rate in the recent half of `draws` minus
+/// its rate in the older half; positive values are trending up.
+///
+/// `draws` must be ordered newest-first. With an odd count the middle draw
+/// belongs to the older half.
+pub fn frequency_trends(draws: &[LottoDraw]) -> BTreeMap<u8, f64> {
+    let (recent, older) = draws.split_at(draws.len() / 2);
+
+    let rates = |half: &[LottoDraw]| {
+        number_frequencies(half)
+            .into_iter()
+            .map(|(number, frequency)| {
+                let rate = if half.is_empty() {
+                    0.0
+                } else {
+                    f64::from(frequency) / half.len() as f64
+                };
+
+                (number, rate)
+            })
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    let older_rates = rates(older);
+
+    rates(recent)
+        .into_iter()
+        .map(|(number, rate)| (number, rate - older_rates[&number]))
+        .collect()
+}
+
+/// Draws whose main-number sum lies between `min` and `max`, both inclusive.
+pub fn draws_with_sum_between(
+    draws: &[LottoDraw],
+    min: u16,
+    max: u16,
+) -> Result<Vec<&LottoDraw>, Errors> {
+    if min > max {
+        return Err(Errors::InvalidArgument(format!(
+            "Expected min <= max, found {} > {}",
+            min, max
+        )));
+    }
+
+    Ok(draws
+        .iter()
+        .filter(|draw| (min..=max).contains(&draw.main_sum()))
+        .collect())
+}
+
+/// Draws since the most recent jackpot winner, with `draws` ordered newest-first.
+///
+/// `None` if no draw had a jackpot winner or a draw before it lacks winner data.
+pub fn draws_since_last_jackpot(draws: &[LottoDraw]) -> Option<u32> {
+    for (index, draw) in draws.iter().enumerate() {
+        if draw.had_jackpot_winner()? {
+            return Some(index as u32);
+        }
+    }
+
+    None
+}
+
+/// Counts of draws whose six main numbers were all odd and all even.
+pub fn parity_extremes(draws: &[LottoDraw]) -> (u32, u32) {
+    draws.iter().fold((0, 0), |(all_odd, all_even), draw| {
+        (
+            all_odd + u32::from(draw.odd_count() == 6),
+            all_even + u32::from(draw.even_count() == 6),
+        )
+    })
+}
+
+fn most_common(values: impl Iterator<Item = u8>) -> Option<u8> {
+    let mut counts = BTreeMap::new();
+
+    for value in values {
+        *counts.entry(value).or_insert(0u32) += 1;
+    }
+
+    // max_by_key keeps the last maximum, so walk from the highest value down
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(value, _)| value)
+}
+
+/// Most frequent lucky number, ties going to the lowest; `None` for no draws.
+pub fn most_common_lucky(draws: &[LottoDraw]) -> Option<u8> {
+    most_common(draws.iter().map(|draw| draw.lucky))
+}
+
+/// Most frequent replay number, ties going to the lowest; `None` for no draws.
+pub fn most_common_replay(draws: &[LottoDraw]) -> Option<u8> {
+    most_common(draws.iter().map(|draw| draw.replay))
+}
+
+/// Mean distance in draws between consecutive appearances of each main number,
+/// e.g. 3.0 for a number drawn in every third draw.
+///
+/// `draws` must be ordered by date, in either direction. `None` for numbers
+/// that appeared fewer than twice.
+pub fn average_appearance_gap(draws: &[LottoDraw]) -> BTreeMap<u8, Option<f64>> {
+    (1..=MAX_NUMBER)
+        .map(|number| {
+            let positions = draws
+                .iter()
+                .enumerate()
+                .filter(|(_, draw)| draw.numbers.contains(&number))
+                .map(|(position, _)| position)
+                .collect::<Vec<_>>();
+
+            let average = match (positions.first(), positions.last()) {
+                (Some(first), Some(last)) if positions.len() > 1 => {
+                    Some((last - first) as f64 / (positions.len() - 1) as f64)
+                }
+                _ => None,
+            };
+
+            (number, average)
+        })
+        .collect()
+}
+
+/// Counts of each replay digit, index = digit. Replay number 10 counts as
+/// digit 0; values outside 1..=10 are ignored.
+pub fn replay_distribution(draws: &[LottoDraw]) -> [u32; 10] {
+    let mut distribution = [0; 10];
+
+    for draw in draws
+        .iter()
+        .filter(|draw| (1..=MAX_REPLAY_NUMBER).contains(&draw.replay))
+    {
+        distribution[usize::from(draw.replay % 10)] += 1;
+    }
+
+    distribution
+}
+
+/// Markdown table of every main number and its frequency, most frequent first
+/// and ties in ascending number order.
+pub fn frequency_report_markdown(draws: &[LottoDraw]) -> String {
+    let mut frequencies = number_frequencies(draws).into_iter().collect::<Vec<_>>();
+    frequencies.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+    let mut report = String::from("| Number | Frequency |\n| ---: | ---: |\n");
+
+    for (number, frequency) in frequencies {
+        report.push_str(&format!("| {} | {} |\n", number, frequency));
+    }
+
+    report
+}
+
+/// Appearances of main number `number` in `(Wednesday, Saturday)` draws.
+pub fn weekday_bias(draws: &[LottoDraw], number: u8) -> Result<(u32, u32), Errors> {
+    if number == 0 || number > MAX_NUMBER {
+        return Err(Errors::InvalidNumber(number));
+    }
+
+    let appearances = draws.iter().filter(|draw| draw.numbers.contains(&number));
+
+    Ok(appearances.fold((0, 0), |(wednesday, saturday), draw| {
+        match draw.date.weekday() {
+            Weekday::Wed => (wednesday + 1, saturday),
+            Weekday::Sat => (wednesday, saturday + 1),
+            _ => (wednesday, saturday),
+        }
+    }))
+}
+
+/// Number of draws in each year, to spot incomplete years.
+pub fn draws_per_year(draws: &[LottoDraw]) -> BTreeMap<i32, u32> {
+    let mut counts = BTreeMap::new();
+
+    for draw in draws {
+        *counts.entry(draw.date.year()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+/// Draws whose lucky number was `lucky`.
+pub fn draws_with_lucky(draws: &[LottoDraw], lucky: u8) -> Result<Vec<&LottoDraw>, Errors> {
+    validate_lucky_number(lucky)?;
+
+    Ok(draws.iter().filter(|draw| draw.lucky == lucky).collect())
+}
+
+/// Average fraction of each draw's main numbers that were also in the draw
+/// before it, with `draws` sorted ascending; `None` for fewer than two draws.
+pub fn repeat_rate(draws: &[LottoDraw]) -> Option<f64> {
+    if draws.len() < 2 {
+        return None;
+    }
+
+    let repeated = draws
+        .windows(2)
+        .map(|pair| f64::from(pair[1].shared_count(&pair[0])) / 6.0)
+        .sum::<f64>();
+
+    Some(repeated / (draws.len() - 1) as f64)
+}
+
+/// Main number with the most draws since its last appearance and that count,
+/// with `draws` ordered newest-first; ties go to the lowest number.
+///
+/// Numbers never drawn count all of `draws`. `None` for no draws.
+pub fn most_overdue(draws: &[LottoDraw]) -> Option<(u8, u32)> {
+    if draws.is_empty() {
+        return None;
+    }
+
+    // max_by_key keeps the last maximum, so walk from the highest number down
+    (1..=MAX_NUMBER)
+        .rev()
+        .map(|number| {
+            let gap = draws
+                .iter()
+                .position(|draw| draw.numbers.contains(&number))
+                .unwrap_or(draws.len());
+
+            (number, gap as u32)
+        })
+        .max_by_key(|(_, gap)| *gap)
+}
+
+/// Chi-squared statistic of the main-number frequencies against a uniform
+/// distribution over the 42 numbers, with 41 degrees of freedom.
+///
+/// Each number is expected `draws.len() * 6 / 42` times. `0.0` for no draws.
+pub fn chi_squared_uniformity(draws: &[LottoDraw]) -> f64 {
+    if draws.is_empty() {
+        return 0.0;
+    }
+
+    let expected = draws.len() as f64 * 6.0 / f64::from(MAX_NUMBER);
+
+    number_frequencies(draws)
+        .values()
+        .map(|observed| (f64::from(*observed) - expected).powi(2) / expected)
+        .sum()
+}
+
+/// Draws with how many main numbers they share with `reference`, most similar
+/// first and ties newest first.
+pub fn rank_by_similarity<'a>(
+    draws: &'a [LottoDraw],
+    reference: &LottoDraw,
+) -> Vec<(&'a LottoDraw, u32)> {
+    let mut ranked = draws
+        .iter()
+        .map(|draw| (draw, draw.shared_count(reference)))
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(a, a_shared), (b, b_shared)| b_shared.cmp(a_shared).then(b.date.cmp(&a.date)));
+
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    #[test]
+    fn pair_cooccurrence_counts_shared_pair() {
+        let draws = [
+            draw(date(2022, 3, 26), [7, 1, 12, 20, 30, 40], 1, 1),
+            draw(date(2022, 3, 30), [2, 7, 1, 21, 31, 41], 2, 2),
+        ];
+
+        let pairs = pair_cooccurrence(&draws);
+
+        assert_eq!(pairs[&(1, 7)], 2);
+        assert_eq!(pairs[&(1, 12)], 1);
+        assert!(!pairs.contains_key(&(7, 1)));
+        assert_eq!(pairs.len(), 29);
+    }
+
+    #[test]
+    fn pair_cooccurrence_edge_cases() {
+        assert!(pair_cooccurrence(&[]).is_empty());
+
+        let pairs = pair_cooccurrence(&[draw(date(2022, 3, 30), [42, 1, 2, 3, 4, 41], 1, 1)]);
+
+        // C(6, 2) pairs of a single draw, including the extremes
+        assert_eq!(pairs.len(), 15);
+        assert_eq!(pairs[&(1, 42)], 1);
+        assert_eq!(pairs[&(41, 42)], 1);
+        assert!(pairs.values().all(|count| *count == 1));
+    }
+
+    #[test]
+    fn overdue_scores_on_crafted_history() {
+        let draws = [
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 26), [7, 8, 9, 10, 11, 12], 1, 1),
+            draw(date(2022, 3, 23), [13, 14, 15, 16, 17, 18], 1, 1),
+        ];
+
+        let scores = overdue_scores(&draws);
+
+        // expected_gap is 42 / 6 = 7 draws
+        assert_eq!(scores.len(), 42);
+        assert!(scores[&1].abs() < 1e-9);
+        assert!((scores[&7] - 1.0 / 7.0).abs() < 1e-9);
+        assert!((scores[&13] - 2.0 / 7.0).abs() < 1e-9);
+        assert!((scores[&42] - 3.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn overdue_scores_edge_cases() {
+        assert!(overdue_scores(&[]).is_empty());
+
+        let scores = overdue_scores(&[draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1)]);
+
+        assert_eq!(
+            scores.keys().copied().collect::<Vec<_>>(),
+            (1..=42).collect::<Vec<_>>()
+        );
+        assert!(scores[&1].abs() < 1e-9);
+        assert!(scores[&42].abs() < 1e-9);
+        assert!((scores[&41] - 1.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn longest_absence_finds_longest_gap() {
+        let draws = [
+            draw(date(2022, 3, 16), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 19), [2, 3, 4, 5, 6, 7], 1, 1),
+            draw(date(2022, 3, 23), [2, 3, 4, 5, 6, 8], 1, 1),
+            draw(date(2022, 3, 26), [2, 3, 4, 5, 6, 9], 1, 1),
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1),
+        ];
+
+        let absence = longest_absence(&draws);
+
+        assert_eq!(absence[&1], 3);
+        assert_eq!(absence[&2], 0);
+        assert_eq!(absence[&8], 2);
+        assert_eq!(absence[&42], 5);
+    }
+
+    #[test]
+    fn longest_absence_edge_cases() {
+        let empty = longest_absence(&[]);
+        assert_eq!(empty.len(), 42);
+        assert!(empty.values().all(|streak| *streak == 0));
+
+        let single = longest_absence(&[draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1)]);
+        assert_eq!(single[&1], 0);
+        assert_eq!(single[&42], 0);
+        assert_eq!(single[&41], 1);
+    }
+
+    #[test]
+    fn filter_by_weekday_separates_draw_days() {
+        let draws = [
+            draw(date(2022, 3, 23), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 26), [2, 3, 4, 5, 6, 7], 1, 1),
+            draw(date(2022, 3, 30), [3, 4, 5, 6, 7, 8], 1, 1),
+        ];
+        let dates = |draws: Vec<&LottoDraw>| draws.iter().map(|draw| draw.date).collect::<Vec<_>>();
+
+        assert_eq!(
+            dates(filter_by_weekday(&draws, Weekday::Wed)),
+            [date(2022, 3, 23), date(2022, 3, 30)]
+        );
+        assert_eq!(
+            dates(filter_by_weekday(&draws, Weekday::Sat)),
+            [date(2022, 3, 26)]
+        );
+        assert!(filter_by_weekday(&draws, Weekday::Mon).is_empty());
+        assert!(filter_by_weekday(&[], Weekday::Wed).is_empty());
+    }
+
+    #[test]
+    fn sum_histogram_buckets_by_lower_bound() {
+        let draws = [
+            // sum 21
+            draw(date(2022, 3, 23), [1, 2, 3, 4, 5, 6], 1, 1),
+            // sum 27
+            draw(date(2022, 3, 26), [2, 3, 4, 5, 6, 7], 1, 1),
+            // sum 207
+            draw(date(2022, 3, 30), [32, 33, 34, 35, 36, 37], 1, 1),
+        ];
+
+        let histogram = sum_histogram(&draws, 10).unwrap();
+
+        assert_eq!(histogram, BTreeMap::from([(20, 2), (200, 1)]));
+        assert!(matches!(
+            sum_histogram(&draws, 0),
+            Err(Errors::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn sum_histogram_edge_cases() {
+        assert!(sum_histogram(&[], 10).unwrap().is_empty());
+
+        // smallest (21) and largest (237) possible sums
+        let draws = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 30), [37, 38, 39, 40, 41, 42], 1, 1),
+        ];
+
+        assert_eq!(
+            sum_histogram(&draws, 1).unwrap(),
+            BTreeMap::from([(21, 1), (237, 1)])
+        );
+        assert_eq!(
+            sum_histogram(&draws, 300).unwrap(),
+            BTreeMap::from([(0, 2)])
+        );
+        assert!(matches!(
+            sum_histogram(&[], 0),
+            Err(Errors::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn audit_history_reports_missing_wednesday() {
+        let draws = [
+            draw(date(2022, 3, 16), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 19), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 4, 2), [1, 2, 3, 4, 5, 6], 1, 1),
+        ];
+
+        let audit = audit_history(&draws, &range(date(2022, 3, 16), date(2022, 3, 30)));
+
+        assert_eq!(audit.missing, [date(2022, 3, 23)]);
+        assert_eq!(audit.out_of_range, [date(2022, 4, 2)]);
+    }
+
+    #[test]
+    fn audit_history_edge_cases() {
+        // 16.03.2022 to 30.03.2022 has five draw days
+        let audit = audit_history(&[], &range(date(2022, 3, 16), date(2022, 3, 30)));
+        assert_eq!(audit.missing.len(), 5);
+        assert!(audit.out_of_range.is_empty());
+
+        // a range without a draw day expects nothing
+        let draws = [draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1)];
+        let audit = audit_history(&draws, &range(date(2022, 3, 17), date(2022, 3, 18)));
+        assert!(audit.missing.is_empty());
+        assert_eq!(audit.out_of_range, [date(2022, 3, 30)]);
+    }
+
+    #[test]
+    fn recent_numbers_of_last_two_draws() {
+        let draws = [
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 26), [4, 5, 6, 7, 8, 9], 1, 1),
+            draw(date(2022, 3, 23), [10, 11, 12, 13, 14, 15], 1, 1),
+            draw(date(2022, 3, 19), [16, 17, 18, 19, 20, 21], 1, 1),
+            draw(date(2022, 3, 16), [22, 23, 24, 25, 26, 27], 1, 1),
+        ];
+
+        assert_eq!(recent_numbers(&draws, 2), (1..=9).collect::<BTreeSet<_>>());
+        assert_eq!(recent_numbers(&draws, 10).len(), 27);
+        assert!(recent_numbers(&draws, 0).is_empty());
+    }
+
+    #[test]
+    fn recent_numbers_edge_cases() {
+        assert!(recent_numbers(&[], 0).is_empty());
+        assert!(recent_numbers(&[], 3).is_empty());
+
+        let draws = [draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1)];
+
+        assert_eq!(
+            recent_numbers(&draws, 1),
+            BTreeSet::from([1, 2, 3, 4, 5, 42])
+        );
+    }
+
+    #[test]
+    fn triplet_cooccurrence_counts_shared_triplet() {
+        let draws = [
+            draw(date(2022, 3, 26), [3, 1, 2, 20, 30, 40], 1, 1),
+            draw(date(2022, 3, 30), [1, 2, 3, 21, 31, 41], 2, 2),
+        ];
+
+        let triplets = triplet_cooccurrence(&draws);
+
+        assert_eq!(triplets[&(1, 2, 3)], 2);
+        assert_eq!(triplets[&(1, 2, 20)], 1);
+        assert!(!triplets.contains_key(&(3, 2, 1)));
+        assert_eq!(triplets.len(), 39);
+    }
+
+    #[test]
+    fn triplet_cooccurrence_edge_cases() {
+        assert!(triplet_cooccurrence(&[]).is_empty());
+
+        let triplets = triplet_cooccurrence(&[draw(date(2022, 3, 30), [42, 1, 2, 3, 4, 41], 1, 1)]);
+
+        // C(6, 3) triplets of a single draw, including the extremes
+        assert_eq!(triplets.len(), 20);
+        assert_eq!(triplets[&(1, 2, 42)], 1);
+        assert_eq!(triplets[&(4, 41, 42)], 1);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn suggest_numbers_is_distinct_and_reproducible() {
+        let history = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8),
+        ];
+
+        let numbers = suggest_numbers(&history, 42);
+
+        assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(numbers
+            .iter()
+            .all(|number| (1..=MAX_NUMBER).contains(number)));
+        assert_eq!(suggest_numbers(&history, 42), numbers);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn suggest_numbers_without_history() {
+        let numbers = suggest_numbers(&[], 0);
+
+        assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(numbers
+            .iter()
+            .all(|number| (1..=MAX_NUMBER).contains(number)));
+    }
+
+    #[test]
+    fn number_frequencies_include_zero_counts() {
+        let draws = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 42], 1, 1),
+            draw(date(2022, 3, 30), [1, 19, 28, 36, 38, 42], 5, 8),
+        ];
+
+        let frequencies = number_frequencies(&draws);
+
+        assert_eq!(frequencies.len(), 42);
+        assert_eq!(frequencies[&1], 2);
+        assert_eq!(frequencies[&42], 2);
+        assert_eq!(frequencies[&19], 1);
+        assert_eq!(frequencies[&41], 0);
+
+        let empty = number_frequencies(&[]);
+        assert_eq!(empty.len(), 42);
+        assert!(empty.values().all(|frequency| *frequency == 0));
+    }
+
+    #[test]
+    fn monthly_frequencies_span_two_months() {
+        let draws = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 30), [1, 7, 8, 9, 10, 11], 1, 1),
+            draw(date(2022, 4, 2), [1, 2, 12, 13, 14, 15], 1, 1),
+        ];
+
+        let months = monthly_frequencies(&draws);
+
+        assert_eq!(
+            months.keys().copied().collect::<Vec<_>>(),
+            [(2022, 3), (2022, 4)]
+        );
+        assert_eq!(months[&(2022, 3)][&1], 2);
+        assert_eq!(months[&(2022, 3)][&12], 0);
+        assert_eq!(months[&(2022, 4)][&1], 1);
+        assert_eq!(months[&(2022, 4)].len(), 42);
+    }
+
+    #[test]
+    fn monthly_frequencies_edge_cases() {
+        assert!(monthly_frequencies(&[]).is_empty());
+
+        // December and January of consecutive years stay apart
+        let draws = [
+            draw(date(2021, 12, 29), [1, 2, 3, 4, 5, 42], 1, 1),
+            draw(date(2022, 1, 1), [1, 2, 3, 4, 5, 42], 1, 1),
+        ];
+
+        let months = monthly_frequencies(&draws);
+
+        assert_eq!(
+            months.keys().copied().collect::<Vec<_>>(),
+            [(2021, 12), (2022, 1)]
+        );
+        assert_eq!(months[&(2021, 12)][&42], 1);
+        assert_eq!(months[&(2022, 1)][&42], 1);
+    }
+
+    #[test]
+    fn never_drawn_finds_omitted_number() {
+        let draws = [
+            draw(date(2022, 3, 12), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 16), [7, 8, 9, 10, 11, 12], 1, 1),
+            draw(date(2022, 3, 19), [1, 14, 15, 16, 17, 18], 1, 1),
+            draw(date(2022, 3, 23), [19, 20, 21, 22, 23, 24], 1, 1),
+            draw(date(2022, 3, 26), [25, 26, 27, 28, 29, 30], 1, 1),
+            draw(date(2022, 3, 30), [31, 32, 33, 34, 35, 36], 1, 1),
+            draw(date(2022, 4, 2), [37, 38, 39, 40, 41, 42], 1, 1),
+        ];
+
+        assert_eq!(never_drawn(&draws), BTreeSet::from([13]));
+        assert_eq!(never_drawn(&[]).len(), 42);
+    }
+
+    #[test]
+    fn never_drawn_includes_unseen_boundaries() {
+        let draws = [draw(date(2022, 3, 30), [2, 3, 4, 5, 6, 7], 1, 1)];
+
+        let unseen = never_drawn(&draws);
+
+        assert_eq!(unseen.len(), 36);
+        assert!(unseen.contains(&1));
+        assert!(unseen.contains(&42));
+        assert!(!unseen.contains(&2));
+        assert_eq!(never_drawn(&[]), (1..=42).collect::<BTreeSet<_>>());
+    }
+
+    #[test]
+    fn rolling_frequencies_with_window_of_two() {
+        let draws = [
+            draw(date(2022, 3, 23), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 26), [1, 7, 8, 9, 10, 11], 1, 1),
+            draw(date(2022, 3, 30), [1, 2, 12, 13, 14, 15], 1, 1),
+            draw(date(2022, 4, 2), [16, 17, 18, 19, 20, 21], 1, 1),
+        ];
+
+        let rolling = rolling_frequencies(&draws, 2).unwrap();
+
+        let dates = rolling.iter().map(|(date, _)| *date).collect::<Vec<_>>();
+        assert_eq!(
+            dates,
+            [date(2022, 3, 26), date(2022, 3, 30), date(2022, 4, 2)]
+        );
+        assert_eq!(rolling[0].1[&1], 2);
+        assert_eq!(rolling[1].1[&1], 2);
+        assert_eq!(rolling[2].1[&1], 1);
+        assert_eq!(rolling[2].1[&2], 1);
+        assert!(matches!(
+            rolling_frequencies(&draws, 0),
+            Err(Errors::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            rolling_frequencies(&draws, 5),
+            Err(Errors::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn rolling_frequencies_edge_cases() {
+        assert!(matches!(
+            rolling_frequencies(&[], 0),
+            Err(Errors::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            rolling_frequencies(&[], 1),
+            Err(Errors::InvalidArgument(_))
+        ));
+
+        let draws = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 42], 1, 1),
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1),
+        ];
+
+        // a window over the whole history yields a single entry
+        let whole = rolling_frequencies(&draws, 2).unwrap();
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].0, date(2022, 3, 30));
+        assert_eq!(whole[0].1[&42], 2);
+
+        assert_eq!(rolling_frequencies(&draws, 1).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn companions_sorted_by_frequency_then_value() {
+        let draws = [
+            draw(date(2022, 3, 19), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 23), [9, 8, 7, 3, 2, 1], 1, 1),
+            draw(date(2022, 3, 26), [1, 2, 10, 11, 12, 13], 1, 1),
+            draw(date(2022, 3, 30), [2, 3, 20, 21, 22, 23], 1, 1),
+        ];
+
+        assert_eq!(companions(&draws, 1, 3).unwrap(), [(2, 3), (3, 2), (4, 1)]);
+        assert!(companions(&draws, 42, 3).unwrap().is_empty());
+        assert!(matches!(
+            companions(&draws, 43, 3),
+            Err(Errors::InvalidNumber(43))
+        ));
+    }
+
+    #[test]
+    fn companions_edge_cases() {
+        let draws = [draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1)];
+
+        assert!(companions(&draws, 1, 0).unwrap().is_empty());
+        assert!(companions(&[], 1, 3).unwrap().is_empty());
+        assert_eq!(
+            companions(&draws, 42, 10).unwrap(),
+            [(1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]
+        );
+        assert!(matches!(
+            companions(&draws, 0, 3),
+            Err(Errors::InvalidNumber(0))
+        ));
+    }
+
+    #[test]
+    fn frequency_gini_of_crafted_distributions() {
+        let uniform = (0..7)
+            .map(|offset| {
+                let first = offset * 6 + 1;
+                let numbers = [first, first + 1, first + 2, first + 3, first + 4, first + 5];
+
+                draw(date(2022, 3, 30), numbers, 1, 1)
+            })
+            .collect::<Vec<_>>();
+        let single = [draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1)];
+
+        assert!(frequency_gini(&uniform).abs() < 1e-12);
+        // 6 numbers drawn once, 36 never: 2 * 6 * 36 / (2 * 42^2 * 6/42) = 6/7
+        assert!((frequency_gini(&single) - 6.0 / 7.0).abs() < 1e-12);
+        assert!(frequency_gini(&[]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn frequency_gini_ignores_history_length() {
+        let once = [draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 42], 1, 1)];
+        let twice = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 42], 1, 1),
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1),
+        ];
+
+        assert!((frequency_gini(&once) - frequency_gini(&twice)).abs() < 1e-12);
+        assert!(frequency_gini(&twice) > 0.0 && frequency_gini(&twice) < 1.0);
+    }
+
+    #[test]
+    fn frequency_trends_favor_recent_half() {
+        // Newest first: 42 only appears in the recent half
+        let draws = [
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1),
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 42], 1, 1),
+            draw(date(2022, 3, 23), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 19), [1, 2, 3, 4, 5, 6], 1, 1),
+        ];
+
+        let trends = frequency_trends(&draws);
+
+        assert!((trends[&42] - 1.0).abs() < 1e-12);
+        assert!((trends[&6] + 1.0).abs() < 1e-12);
+        assert!(trends[&1].abs() < 1e-12);
+        assert!(trends[&20].abs() < 1e-12);
+    }
+
+    #[test]
+    fn frequency_trends_put_middle_draw_in_older_half() {
+        let single = [draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1)];
+
+        let trends = frequency_trends(&single);
+
+        assert_eq!(trends.len(), 42);
+        assert!((trends[&42] + 1.0).abs() < 1e-12);
+        assert!(trends[&20].abs() < 1e-12);
+        assert!(frequency_trends(&[])
+            .values()
+            .all(|trend| trend.abs() < 1e-12));
+    }
+
+    #[test]
+    fn draws_with_sum_in_inclusive_range() {
+        let draws = [
+            // sum 21
+            draw(date(2022, 3, 23), [1, 2, 3, 4, 5, 6], 1, 1),
+            // sum 167
+            draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8),
+            // sum 207
+            draw(date(2022, 4, 2), [32, 33, 34, 35, 36, 37], 1, 1),
+        ];
+
+        assert_eq!(
+            draws_with_sum_between(&draws, 21, 167).unwrap(),
+            [&draws[0], &draws[1]]
+        );
+        assert_eq!(
+            draws_with_sum_between(&draws, 168, 207).unwrap(),
+            [&draws[2]]
+        );
+        assert!(matches!(
+            draws_with_sum_between(&draws, 100, 99),
+            Err(Errors::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn draws_with_sum_accepts_single_value_range() {
+        let draws = [draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8)];
+
+        assert_eq!(
+            draws_with_sum_between(&draws, 167, 167).unwrap(),
+            [&draws[0]]
+        );
+        assert!(draws_with_sum_between(&draws, 168, 168).unwrap().is_empty());
+        assert!(draws_with_sum_between(&[], 0, u16::MAX).unwrap().is_empty());
+    }
+
+    fn with_jackpot_winners(lotto_draw: LottoDraw, winners: u32) -> LottoDraw {
+        LottoDraw {
+            winners_by_class: Some(vec![crate::ClassResult {
+                class: 1,
+                winners,
+                prize_chf: 0.0,
+            }]),
+            ..lotto_draw
+        }
+    }
+
+    #[test]
+    fn draws_since_last_jackpot_counts_newest_first() {
+        let lotto_draw = draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1);
+        let draws = [
+            with_jackpot_winners(lotto_draw.clone(), 0),
+            with_jackpot_winners(lotto_draw.clone(), 0),
+            with_jackpot_winners(lotto_draw.clone(), 1),
+            with_jackpot_winners(lotto_draw.clone(), 0),
+        ];
+
+        assert_eq!(draws_since_last_jackpot(&draws), Some(2));
+        assert_eq!(draws_since_last_jackpot(&draws[..2]), None);
+        assert_eq!(
+            draws_since_last_jackpot(&[lotto_draw, draws[2].clone()]),
+            None
+        );
+    }
+
+    #[test]
+    fn draws_since_last_jackpot_handles_latest_and_empty() {
+        let lotto_draw = draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1);
+
+        assert_eq!(
+            draws_since_last_jackpot(&[with_jackpot_winners(lotto_draw.clone(), 2), lotto_draw]),
+            Some(0)
+        );
+        assert_eq!(draws_since_last_jackpot(&[]), None);
+    }
+
+    #[test]
+    fn parity_extremes_counts_all_odd_and_all_even() {
+        let draws = [
+            draw(date(2022, 3, 19), [1, 3, 5, 7, 9, 11], 1, 1),
+            draw(date(2022, 3, 23), [2, 4, 6, 8, 10, 12], 1, 1),
+            draw(date(2022, 3, 26), [13, 15, 17, 19, 21, 41], 1, 1),
+            draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8),
+        ];
+
+        assert_eq!(parity_extremes(&draws), (2, 1));
+        assert_eq!(parity_extremes(&[]), (0, 0));
+    }
+
+    #[test]
+    fn most_common_lucky_and_replay_break_ties_low() {
+        let draws = [
+            draw(date(2022, 3, 19), [1, 2, 3, 4, 5, 6], 4, 9),
+            draw(date(2022, 3, 23), [1, 2, 3, 4, 5, 6], 2, 9),
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 4, 3),
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 2, 7),
+        ];
+
+        assert_eq!(most_common_lucky(&draws), Some(2));
+        assert_eq!(most_common_replay(&draws), Some(9));
+        assert_eq!(most_common_lucky(&[]), None);
+        assert_eq!(most_common_replay(&[]), None);
+    }
+
+    #[test]
+    fn most_common_of_single_draw_and_boundaries() {
+        let single = [draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 6, 10)];
+        let draws = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 6, 10),
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1),
+        ];
+
+        assert_eq!(most_common_lucky(&single), Some(6));
+        assert_eq!(most_common_replay(&single), Some(10));
+        assert_eq!(most_common_lucky(&draws), Some(1));
+        assert_eq!(most_common_replay(&draws), Some(1));
+    }
+
+    #[test]
+    fn average_appearance_gap_at_known_intervals() {
+        // 7 appears at positions 0, 2 and 6, 8 only once
+        let other = [1, 2, 3, 4, 5, 6];
+        let with_seven = [1, 2, 3, 4, 5, 7];
+        let draws = [
+            draw(date(2022, 3, 12), with_seven, 1, 1),
+            draw(date(2022, 3, 16), other, 1, 1),
+            draw(date(2022, 3, 19), with_seven, 1, 1),
+            draw(date(2022, 3, 23), other, 1, 1),
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 8], 1, 1),
+            draw(date(2022, 3, 30), other, 1, 1),
+            draw(date(2022, 4, 2), with_seven, 1, 1),
+        ];
+
+        let gaps = average_appearance_gap(&draws);
+
+        assert!((gaps[&7].unwrap() - 3.0).abs() < 1e-12);
+        assert!((gaps[&1].unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(gaps[&8], None);
+        assert_eq!(gaps[&42], None);
+    }
+
+    #[test]
+    fn average_appearance_gap_of_short_histories() {
+        let gaps = average_appearance_gap(&[]);
+        assert_eq!(gaps.len(), 42);
+        assert!(gaps.values().all(Option::is_none));
+
+        let single = [draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1)];
+        assert_eq!(average_appearance_gap(&single)[&42], None);
+    }
+
+    #[test]
+    fn replay_distribution_index_is_digit() {
+        let draws = [
+            draw(date(2022, 3, 19), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 23), [1, 2, 3, 4, 5, 6], 1, 8),
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 1, 10),
+            draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8),
+            // Invalid replay numbers are ignored
+            draw(date(2022, 4, 2), [1, 2, 3, 4, 5, 6], 1, 0),
+            draw(date(2022, 4, 6), [1, 2, 3, 4, 5, 6], 1, 11),
+        ];
+
+        assert_eq!(replay_distribution(&draws), [1, 1, 0, 0, 0, 0, 0, 0, 2, 0]);
+        assert_eq!(replay_distribution(&[]), [0; 10]);
+    }
+
+    #[test]
+    fn sum_mode_and_least_break_ties_low() {
+        let draws = [
+            // sums 21, 27, 207, 21, 27, 30
+            draw(date(2022, 3, 16), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 19), [2, 3, 4, 5, 6, 7], 1, 1),
+            draw(date(2022, 3, 23), [32, 33, 34, 35, 36, 37], 1, 1),
+            draw(date(2022, 3, 26), [6, 5, 4, 3, 2, 1], 1, 1),
+            draw(date(2022, 3, 30), [7, 6, 5, 4, 3, 2], 1, 1),
+            draw(date(2022, 4, 2), [1, 2, 3, 4, 5, 15], 1, 1),
+        ];
+
+        assert_eq!(sum_mode(&draws), Some((21, 2)));
+        assert_eq!(sum_least(&draws), Some((30, 1)));
+        assert_eq!(sum_mode(&[]), None);
+        assert_eq!(sum_least(&[]), None);
+    }
+
+    #[test]
+    fn sum_mode_and_least_of_single_draw() {
+        let single = [draw(date(2022, 3, 30), [37, 38, 39, 40, 41, 42], 1, 1)];
+
+        assert_eq!(sum_mode(&single), Some((237, 1)));
+        assert_eq!(sum_least(&single), Some((237, 1)));
+    }
+
+    #[test]
+    fn frequency_report_has_row_per_number() {
+        let draws = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8),
+        ];
+
+        let report = frequency_report_markdown(&draws);
+        let lines = report.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2 + 42);
+        assert_eq!(lines[0], "| Number | Frequency |");
+        assert_eq!(lines[2], "| 5 | 2 |");
+        assert_eq!(lines[3], "| 1 | 1 |");
+        assert_eq!(lines[43], "| 42 | 0 |");
+    }
+
+    #[test]
+    fn frequency_report_of_no_draws_lists_numbers_ascending() {
+        let report = frequency_report_markdown(&[]);
+        let lines = report.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2 + 42);
+        assert_eq!(lines[1], "| ---: | ---: |");
+        assert_eq!(lines[2], "| 1 | 0 |");
+        assert_eq!(lines[43], "| 42 | 0 |");
+    }
+
+    #[test]
+    fn weekday_bias_over_mixed_history() {
+        let draws = [
+            draw(date(2022, 3, 19), [7, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 23), [7, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 26), [7, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1),
+        ];
+
+        assert_eq!(weekday_bias(&draws, 7).unwrap(), (1, 2));
+        assert_eq!(weekday_bias(&draws, 2).unwrap(), (2, 2));
+        assert!(matches!(
+            weekday_bias(&draws, 0),
+            Err(Errors::InvalidNumber(0))
+        ));
+    }
+
+    #[test]
+    fn weekday_bias_boundaries() {
+        let draws = [
+            draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 1, 1),
+            // a Friday is neither draw day
+            draw(date(2022, 4, 1), [1, 2, 3, 4, 5, 42], 1, 1),
+        ];
+
+        assert_eq!(weekday_bias(&draws, 42).unwrap(), (1, 0));
+        assert_eq!(weekday_bias(&draws, 1).unwrap(), (1, 0));
+        assert_eq!(weekday_bias(&[], 1).unwrap(), (0, 0));
+        assert!(matches!(
+            weekday_bias(&draws, 43),
+            Err(Errors::InvalidNumber(43))
+        ));
+    }
+
+    #[test]
+    fn longest_presence_finds_three_in_a_row() {
+        let draws = [
+            draw(date(2022, 3, 16), [7, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 19), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 23), [7, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 26), [7, 1, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 3, 30), [7, 1, 2, 4, 5, 6], 1, 1),
+        ];
+
+        let presence = longest_presence(&draws);
+
+        assert_eq!(presence[&7], 3);
+        assert_eq!(presence[&1], 2);
+        assert_eq!(presence[&2], 3);
+        assert_eq!(presence[&4], 5);
+        assert_eq!(presence[&42], 0);
+    }
+
+    #[test]
+    fn longest_presence_of_no_draws_is_zero() {
+        let presence = longest_presence(&[]);
+
+        assert_eq!(presence.len(), 42);
+        assert!(presence.values().all(|streak| *streak == 0));
+    }
+
+    #[test]
+    fn draws_per_year_spanning_two_years() {
+        let draws = [
+            draw(date(2021, 12, 25), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2021, 12, 29), [1, 2, 3, 4, 5, 6], 1, 1),
+            draw(date(2022, 1, 1), [1, 2, 3, 4, 5, 6], 1, 1),
+        ];
+
+        assert_eq!(
+            draws_per_year(&draws),
+            BTreeMap::from([(2021, 2), (2022, 1)])
+        );
+    }
+
+    #[test]
+    fn draws_per_year_of_no_draws_is_empty() {
+        assert!(draws_per_year(&[]).is_empty());
+    }
+
+    #[test]
+    fn draws_with_lucky_filters_history() {
+        let draws = [
+            draw(date(2022, 3, 23), [1, 2, 3, 4, 5, 6], 5, 1),
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 2, 1),
+            draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8),
+        ];
+
+        let lucky_five = draws_with_lucky(&draws, 5).unwrap();
+
+        assert_eq!(lucky_five, [&draws[0], &draws[2]]);
+        assert!(draws_with_lucky(&draws, 6).unwrap().is_empty());
+        assert!(matches!(
+            draws_with_lucky(&draws, 0),
+            Err(Errors::InvalidLuckyNumber(0))
+        ));
+        assert!(matches!(
+            draws_with_lucky(&draws, 7),
+            Err(Errors::InvalidLuckyNumber(7))
+        ));
+    }
+
+    #[test]
+    fn draws_with_lucky_of_no_draws_is_empty() {
+        assert!(draws_with_lucky(&[], 1).unwrap().is_empty());
+        assert!(draws_with_lucky(&[], 6).unwrap().is_empty());
+    }
+
+    #[test]
+    fn repeat_rate_averages_consecutive_draws() {
+        let draws = [
+            draw(date(2022, 3, 23), [1, 2, 3, 4, 5, 6], 1, 1),
+            // half of the numbers repeat
+            draw(date(2022, 3, 26), [1, 2, 3, 7, 8, 9], 1, 1),
+            // all of them repeat
+            draw(date(2022, 3, 30), [9, 8, 7, 3, 2, 1], 1, 1),
+            // none repeat
+            draw(date(2022, 4, 2), [10, 11, 12, 13, 14, 15], 1, 1),
+        ];
+
+        let rate = repeat_rate(&draws).unwrap();
+
+        assert!((rate - 0.5).abs() < 1e-9);
+        assert!((repeat_rate(&draws[..3]).unwrap() - 0.75).abs() < 1e-9);
+        assert_eq!(repeat_rate(&draws[..1]), None);
+        assert_eq!(repeat_rate(&[]), None);
+    }
+
+    #[test]
+    fn repeat_rate_of_identical_draws_is_one() {
+        let draws = [
+            draw(date(2022, 3, 26), [1, 2, 3, 40, 41, 42], 1, 1),
+            draw(date(2022, 3, 30), [1, 2, 3, 40, 41, 42], 6, 10),
+        ];
+
+        assert!((repeat_rate(&draws).unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn most_overdue_number() {
+        // Newest first, drawing every number once
+        let draws = (0..7u8)
+            .map(|i| {
+                let numbers = [1, 2, 3, 4, 5, 6].map(|n| 6 * i + n);
+                draw(
+                    date(2022, 3, 30) - chrono::Duration::days(i64::from(i)),
+                    numbers,
+                    1,
+                    1,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // 37..=42 were drawn six draws ago, the tie goes to 37
+        assert_eq!(most_overdue(&draws), Some((37, 6)));
+        // 19..=42 were never drawn in the first three draws
+        assert_eq!(most_overdue(&draws[..3]), Some((19, 3)));
+        assert_eq!(most_overdue(&[]), None);
+    }
+
+    #[test]
+    fn most_overdue_of_a_single_draw() {
+        let draws = [draw(date(2022, 3, 30), [1, 2, 3, 40, 41, 42], 1, 1)];
+
+        // Every number not drawn counts the single draw, the lowest is 4
+        assert_eq!(most_overdue(&draws), Some((4, 1)));
+    }
+
+    #[test]
+    fn chi_squared_of_crafted_distributions() {
+        // Seven draws expect every number once
+        let uniform = (0..7u8)
+            .map(|i| {
+                draw(
+                    date(2022, 3, 30),
+                    [1, 2, 3, 4, 5, 6].map(|n| 6 * i + n),
+                    1,
+                    1,
+                )
+            })
+            .collect::<Vec<_>>();
+        let repeated = vec![draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 1, 1); 7];
+
+        assert!(chi_squared_uniformity(&uniform).abs() < 1e-9);
+        // 6 numbers seen 7 times: 6 * (7 - 1)² / 1, and 36 unseen: 36 * (0 - 1)² / 1
+        assert!((chi_squared_uniformity(&repeated) - 252.0).abs() < 1e-9);
+        assert!(chi_squared_uniformity(&[]).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn chi_squared_of_a_single_draw() {
+        let draws = [draw(date(2022, 3, 30), [1, 2, 3, 40, 41, 42], 1, 1)];
+        let expected = 6.0 / 42.0;
+        // 6 numbers seen once and 36 unseen
+        let statistic =
+            6.0 * (1.0 - expected).powi(2) / expected + 36.0 * expected.powi(2) / expected;
+
+        assert!((chi_squared_uniformity(&draws) - statistic).abs() < 1e-9);
+        assert!((statistic - 36.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rank_by_similarity_breaks_ties_by_date() {
+        let reference = draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8);
+        let draws = [
+            draw(date(2022, 3, 16), [1, 2, 3, 4, 6, 7], 1, 1),
+            draw(date(2022, 3, 19), [5, 19, 28, 1, 2, 3], 1, 1),
+            draw(date(2022, 3, 23), [41, 2, 3, 4, 6, 7], 1, 1),
+            draw(date(2022, 3, 26), [5, 19, 28, 4, 6, 7], 1, 1),
+            reference.clone(),
+        ];
+
+        let ranked = rank_by_similarity(&draws, &reference)
+            .into_iter()
+            .map(|(draw, shared)| (draw.date, shared))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ranked,
+            [
+                (date(2022, 3, 30), 6),
+                (date(2022, 3, 26), 3),
+                (date(2022, 3, 19), 3),
+                (date(2022, 3, 23), 1),
+                (date(2022, 3, 16), 0),
+            ]
+        );
+        assert!(rank_by_similarity(&[], &reference).is_empty());
+    }
+
+    #[test]
+    fn rank_by_similarity_of_a_single_disjoint_draw() {
+        let reference = draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8);
+        let draws = [draw(date(2022, 3, 26), [1, 2, 3, 4, 6, 7], 5, 8)];
+
+        // Lucky and replay numbers don't count
+        assert_eq!(rank_by_similarity(&draws, &reference), [(&draws[0], 0)]);
+    }
+}
@@ -0,0 +1,297 @@
+//! Fixtures and a minimal HTTP server for the unit tests.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use reqwest::{StatusCode, Url};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::{DrawDateRange, LottoDraw, SwissLottoClient, SwissLottoClientBuilder};
+
+/// Saved winning-numbers page of the draw on 30.03.2022.
+pub const FIXTURE: &str = include_str!("../benches/winning-numbers.html");
+
+pub fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+pub fn range(start: NaiveDate, end: NaiveDate) -> DrawDateRange {
+    DrawDateRange::new(start, end).unwrap()
+}
+
+pub fn draw(date: NaiveDate, numbers: [u8; 6], lucky: u8, replay: u8) -> LottoDraw {
+    LottoDraw {
+        date,
+        numbers,
+        lucky,
+        replay,
+        winners_by_class: None,
+    }
+}
+
+/// A minimal winning-numbers page showing `draw`, without a winners table.
+pub fn draw_page(draw: &LottoDraw) -> String {
+    draw_page_with_winners(draw, &[])
+}
+
+/// Like [`draw_page`], with a winners table row per `(correct numbers, winners, prize)`.
+pub fn draw_page_with_winners(draw: &LottoDraw, rows: &[(&str, &str, &str)]) -> String {
+    let number = |kind: &str, number: u8| {
+        format!(
+            "<li class=\"actual-numbers__number actual-numbers__number___{}\"><span>{}</span></li>",
+            kind, number
+        )
+    };
+
+    let mut numbers = draw
+        .numbers
+        .iter()
+        .map(|n| number("normal", *n))
+        .collect::<String>();
+    numbers.push_str(&number("lucky", draw.lucky));
+    numbers.push_str(&number("replay", draw.replay));
+
+    let table = if rows.is_empty() {
+        String::new()
+    } else {
+        let rows = rows
+            .iter()
+            .map(|(correct, winners, prize)| {
+                format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    correct, winners, prize
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            "<table class=\"quotes__game-table\"><tbody>{}</tbody></table>",
+            rows
+        )
+    };
+
+    let formatted_date = draw.date.format("%d.%m.%Y");
+
+    format!(
+        "<html><body>\
+         <form id=\"swissLottoStatisticsQuotesForm\" method=\"post\">\
+         <input name=\"formattedFilterDate\" type=\"text\" id=\"formattedFilterDate\" value=\"{}\">\
+         <input name=\"filterDate\" type=\"hidden\" value=\"\">\
+         <input name=\"currentDate\" type=\"hidden\" value=\"{}\">\
+         </form>\
+         <div class=\"filter-results\">\
+         <div class=\"quotes__game\"><ul class=\"actual-numbers__numbers\">{}</ul></div>\
+         {}\
+         </div>\
+         </body></html>",
+        formatted_date, formatted_date, numbers, table
+    )
+}
+
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub method: String,
+    pub path: String,
+    /// Keyed by lowercase header name.
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl MockRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// The url-encoded form in the body.
+    pub fn form(&self) -> HashMap<String, String> {
+        Url::parse(&format!("http://mock/?{}", self.body))
+            .map(|url| url.query_pairs().into_owned().collect())
+            .unwrap_or_default()
+    }
+}
+
+pub struct MockResponse {
+    status: StatusCode,
+    headers: Vec<(String, String)>,
+    body: String,
+    delay: Duration,
+}
+
+impl MockResponse {
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self {
+            body: body.into(),
+            ..Self::status(200)
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self {
+            status: StatusCode::from_u16(status).unwrap(),
+            headers: Vec::new(),
+            body: String::new(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Waits `delay` before answering.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    async fn write_to(self, stream: &mut TcpStream) {
+        tokio::time::sleep(self.delay).await;
+
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status.as_u16(),
+            self.status.canonical_reason().unwrap_or_default(),
+            self.body.len()
+        );
+
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+
+        head.push_str("\r\n");
+
+        // The client may have given up already
+        let _ = stream.write_all(head.as_bytes()).await;
+        let _ = stream.write_all(self.body.as_bytes()).await;
+        let _ = stream.shutdown().await;
+    }
+}
+
+type Handler = dyn Fn(&MockRequest) -> MockResponse + Send + Sync;
+
+/// Answers every request on a local port with `handler`, recording the requests.
+pub struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+impl MockServer {
+    pub async fn start(
+        handler: impl Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handler: Arc<Handler> = Arc::new(handler);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let handler = handler.clone();
+                let recorded = recorded.clone();
+
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+
+                    if let Some(request) = read_request(&mut stream).await {
+                        let response = handler(&request);
+                        recorded.lock().unwrap().push(request);
+                        response.write_to(stream.get_mut()).await;
+                    }
+                });
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// A client builder sending the swisslos pages' requests to this server.
+    pub fn builder(&self) -> SwissLottoClientBuilder {
+        SwissLottoClient::builder()
+            .no_proxy(true)
+            .base_url(self.url())
+    }
+
+    pub fn client(&self) -> SwissLottoClient {
+        self.builder().build().unwrap()
+    }
+}
+
+async fn read_request(stream: &mut BufReader<TcpStream>) -> Option<MockRequest> {
+    let mut request_line = String::new();
+    stream.read_line(&mut request_line).await.ok()?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut headers = HashMap::new();
+
+    loop {
+        let mut line = String::new();
+        stream.read_line(&mut line).await.ok()?;
+
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            break;
+        }
+
+        let (name, value) = line.split_once(':')?;
+        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    stream.read_exact(&mut body).await.ok()?;
+
+    Some(MockRequest {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// Serves `draws` like the swisslos archive: a GET shows the latest draw, a
+/// POST the latest draw on or before `formattedFilterDate`, or else the earliest.
+pub fn archive(draws: Vec<LottoDraw>) -> impl Fn(&MockRequest) -> MockResponse + Send + Sync {
+    move |request| {
+        let requested = request
+            .form()
+            .get("formattedFilterDate")
+            .and_then(|value| NaiveDate::parse_from_str(value, "%d.%m.%Y").ok())
+            .filter(|_| request.method == "POST");
+
+        let draw = match requested {
+            Some(date) => draws
+                .iter()
+                .filter(|draw| draw.date <= date)
+                .max_by_key(|draw| draw.date)
+                .or_else(|| draws.iter().min_by_key(|draw| draw.date)),
+            None => draws.iter().max_by_key(|draw| draw.date),
+        };
+
+        match draw {
+            Some(draw) => MockResponse::ok(draw_page(draw)),
+            None => MockResponse::status(404),
+        }
+    }
+}
@@ -0,0 +1,856 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{Errors, LottoDraw, MAX_LUCKY_NUMBER, MAX_NUMBER};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DrawMatch {
+    pub main_matches: u8,
+    pub lucky_matched: bool,
+    pub winning_class: Option<u8>,
+}
+
+impl DrawMatch {
+    pub fn new(main_matches: u8, lucky_matched: bool) -> Self {
+        Self {
+            main_matches,
+            lucky_matched,
+            winning_class: winning_class(main_matches, lucky_matched),
+        }
+    }
+
+    /// Official label of the prize class, e.g. "5 + lucky number".
+    pub fn class_description(&self) -> &'static str {
+        match self.winning_class {
+            Some(1) => "6 + lucky number",
+            Some(2) => "6 numbers",
+            Some(3) => "5 + lucky number",
+            Some(4) => "5 numbers",
+            Some(5) => "4 + lucky number",
+            Some(6) => "4 numbers",
+            Some(7) => "3 + lucky number",
+            Some(8) => "3 numbers",
+            _ => "no win",
+        }
+    }
+}
+
+/// Swiss Lotto prize class (1 = 6 + lucky number, 8 = 3 numbers), if any.
+pub fn winning_class(main_matches: u8, lucky_matched: bool) -> Option<u8> {
+    match (main_matches, lucky_matched) {
+        (6, true) => Some(1),
+        (6, false) => Some(2),
+        (5, true) => Some(3),
+        (5, false) => Some(4),
+        (4, true) => Some(5),
+        (4, false) => Some(6),
+        (3, true) => Some(7),
+        (3, false) => Some(8),
+        _ => None,
+    }
+}
+
+pub(crate) fn validate_main_numbers(numbers: &[u8]) -> Result<(), Errors> {
+    let mut seen = [false; MAX_NUMBER as usize + 1];
+
+    for &number in numbers {
+        if number == 0 || number > MAX_NUMBER {
+            return Err(Errors::InvalidNumber(number));
+        }
+
+        if seen[number as usize] {
+            return Err(Errors::DuplicateNumber(number));
+        }
+
+        seen[number as usize] = true;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_lucky_number(lucky: u8) -> Result<(), Errors> {
+    if lucky == 0 || lucky > MAX_LUCKY_NUMBER {
+        return Err(Errors::InvalidLuckyNumber(lucky));
+    }
+
+    Ok(())
+}
+
+impl LottoDraw {
+    pub fn evaluate_ticket(&self, ticket: &[u8; 6], lucky: u8) -> Result<DrawMatch, Errors> {
+        validate_main_numbers(ticket)?;
+        validate_lucky_number(lucky)?;
+
+        Ok(self.match_ticket(ticket, lucky))
+    }
+
+    /// Main numbers as a bitmask, with bit `n` set if `n` was drawn.
+    pub fn number_mask(&self) -> u64 {
+        mask_of(&self.numbers)
+    }
+
+    /// Main numbers drawn in both `self` and `other`, ascending.
+    pub fn shared_numbers(&self, other: &LottoDraw) -> Vec<u8> {
+        let mut shared = self
+            .numbers
+            .iter()
+            .copied()
+            .filter(|number| other.numbers.contains(number))
+            .collect::<Vec<_>>();
+        shared.sort_unstable();
+
+        shared
+    }
+
+    /// Main numbers in `self` but not in `previous`, ascending.
+    pub fn new_numbers_vs(&self, previous: &LottoDraw) -> Vec<u8> {
+        let mut new_numbers = self
+            .numbers
+            .iter()
+            .copied()
+            .filter(|number| !previous.numbers.contains(number))
+            .collect::<Vec<_>>();
+        new_numbers.sort_unstable();
+
+        new_numbers
+    }
+
+    /// Main numbers in `previous` but no longer in `self`, ascending.
+    pub fn dropped_numbers_vs(&self, previous: &LottoDraw) -> Vec<u8> {
+        previous.new_numbers_vs(self)
+    }
+
+    /// Number of main numbers drawn in both `self` and `other`, via their bitmasks.
+    pub fn shared_count(&self, other: &LottoDraw) -> u32 {
+        (self.number_mask() & other.number_mask()).count_ones()
+    }
+
+    /// Ascending main numbers of a mask with exactly six bits set in 1..=42.
+    pub fn numbers_from_mask(mask: u64) -> Option<[u8; 6]> {
+        let mut numbers = [0; 6];
+        let mut slots = numbers.iter_mut();
+
+        for number in (1..=MAX_NUMBER).filter(|number| mask & (1 << number) != 0) {
+            *slots.next()? = number;
+        }
+
+        if slots.next().is_some() || mask.count_ones() != 6 {
+            return None;
+        }
+
+        Some(numbers)
+    }
+
+    pub(crate) fn match_ticket(&self, ticket: &[u8; 6], lucky: u8) -> DrawMatch {
+        let main_matches = ticket
+            .iter()
+            .filter(|number| self.numbers.contains(number))
+            .count() as u8;
+
+        DrawMatch::new(main_matches, self.lucky == lucky)
+    }
+}
+
+/// Evaluates every 6-number combination of a system ticket against `draw`.
+///
+/// Results are in lexicographic order of the combinations' positions in
+/// `system_numbers`.
+pub fn evaluate_system(
+    draw: &LottoDraw,
+    system_numbers: &[u8],
+    lucky: u8,
+) -> Result<Vec<DrawMatch>, Errors> {
+    if system_numbers.len() < 6 {
+        return Err(Errors::InvalidArgument(format!(
+            "Expected at least 6 system numbers, found {}",
+            system_numbers.len()
+        )));
+    }
+
+    validate_main_numbers(system_numbers)?;
+    validate_lucky_number(lucky)?;
+
+    Ok(combinations(system_numbers)
+        .iter()
+        .map(|ticket| draw.match_ticket(ticket, lucky))
+        .collect())
+}
+
+pub(crate) fn combinations(numbers: &[u8]) -> Vec<[u8; 6]> {
+    let mut combinations = Vec::new();
+    let count = numbers.len();
+
+    if count < 6 {
+        return combinations;
+    }
+
+    let mut indices = [0, 1, 2, 3, 4, 5];
+
+    loop {
+        let mut ticket = [0; 6];
+        for (slot, index) in ticket.iter_mut().zip(indices.iter()) {
+            *slot = numbers[*index];
+        }
+        combinations.push(ticket);
+
+        let mut position = 6;
+        while position > 0 && indices[position - 1] == position - 1 + count - 6 {
+            position -= 1;
+        }
+
+        if position == 0 {
+            return combinations;
+        }
+
+        indices[position - 1] += 1;
+
+        let mut value = indices[position - 1];
+        for index in indices.iter_mut().skip(position) {
+            value += 1;
+            *index = value;
+        }
+    }
+}
+
+fn binomial(n: u64, k: u64) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Hypergeometric probability that a random ticket hits exactly
+/// `main_matches` main numbers and the given lucky number outcome.
+pub fn match_probability(main_matches: u8, lucky_matched: bool) -> Result<f64, Errors> {
+    if main_matches > 6 {
+        return Err(Errors::InvalidArgument(format!(
+            "Expected at most 6 main matches, found {}",
+            main_matches
+        )));
+    }
+
+    let main_matches = u64::from(main_matches);
+    let drawn = 6;
+    let undrawn = u64::from(MAX_NUMBER) - drawn;
+
+    let main = binomial(drawn, main_matches) * binomial(undrawn, drawn - main_matches)
+        / binomial(u64::from(MAX_NUMBER), drawn);
+    let lucky = 1.0 / f64::from(MAX_LUCKY_NUMBER);
+
+    if lucky_matched {
+        Ok(main * lucky)
+    } else {
+        Ok(main * (1.0 - lucky))
+    }
+}
+
+/// Probability that a given main number is among the six drawn, 6/42.
+///
+/// Every draw is independent, so past draws do not change this.
+pub fn single_number_draw_probability() -> f64 {
+    6.0 / f64::from(MAX_NUMBER)
+}
+
+/// Probability that a given main number is drawn at least once in the next
+/// `n` independent draws.
+pub fn at_least_once_in(n: u32) -> f64 {
+    1.0 - (1.0 - single_number_draw_probability()).powf(f64::from(n))
+}
+
+/// Fraction of all C(42, 6) tickets covered by playing every six-number
+/// combination of `system_numbers`.
+pub fn system_coverage(system_numbers: &[u8]) -> Result<f64, Errors> {
+    if system_numbers.len() < 6 {
+        return Err(Errors::InvalidArgument(format!(
+            "Expected at least 6 system numbers, found {}",
+            system_numbers.len()
+        )));
+    }
+
+    validate_main_numbers(system_numbers)?;
+
+    Ok(binomial(system_numbers.len() as u64, 6) / binomial(u64::from(MAX_NUMBER), 6))
+}
+
+/// Probability of each winning class for a single ticket, keyed by class.
+///
+/// The odds do not depend on the picked numbers; the ticket is only validated.
+pub fn ticket_odds(ticket: &[u8; 6], lucky: u8) -> Result<BTreeMap<u8, f64>, Errors> {
+    validate_main_numbers(ticket)?;
+    validate_lucky_number(lucky)?;
+
+    let mut odds = BTreeMap::new();
+
+    for main_matches in 0..=6 {
+        for lucky_matched in [true, false] {
+            if let Some(class) = winning_class(main_matches, lucky_matched) {
+                odds.insert(class, match_probability(main_matches, lucky_matched)?);
+            }
+        }
+    }
+
+    Ok(odds)
+}
+
+/// Plays a ticket against every draw, returning the draws where it won a class.
+pub fn backtest_ticket(
+    ticket: &[u8; 6],
+    lucky: u8,
+    draws: &[LottoDraw],
+) -> Result<Vec<(NaiveDate, DrawMatch)>, Errors> {
+    validate_main_numbers(ticket)?;
+    validate_lucky_number(lucky)?;
+
+    Ok(draws
+        .iter()
+        .map(|draw| (draw.date, draw.match_ticket(ticket, lucky)))
+        .filter(|(_, draw_match)| draw_match.winning_class.is_some())
+        .collect())
+}
+
+/// Stake and winnings of a ticket played in every draw of a history.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct RoiReport {
+    pub draws: u32,
+    /// In CHF, converted from the stake per draw given in Rappen (1/100 CHF).
+    pub total_stake_chf: f64,
+    pub total_winnings_chf: f64,
+    pub winning_draws: u32,
+    /// Wins in draws without a parsed prize for the class, counted as zero
+    /// in `total_winnings_chf`.
+    pub unpriced_wins: u32,
+}
+
+impl RoiReport {
+    pub fn net_chf(&self) -> f64 {
+        self.total_winnings_chf - self.total_stake_chf
+    }
+}
+
+/// Plays a ticket against every draw for `stake_per_draw` Rappen (1/100 CHF),
+/// summing the prizes of the classes it won.
+///
+/// Prizes come from each draw's `winners_by_class`, which is only present when
+/// the winners table was parsed; wins without it are counted in
+/// `unpriced_wins`, so winnings may be understated.
+pub fn backtest_roi(
+    ticket: &[u8; 6],
+    lucky: u8,
+    stake_per_draw: u64,
+    draws: &[LottoDraw],
+) -> Result<RoiReport, Errors> {
+    validate_main_numbers(ticket)?;
+    validate_lucky_number(lucky)?;
+
+    let mut report = RoiReport {
+        draws: draws.len() as u32,
+        total_stake_chf: stake_per_draw.saturating_mul(draws.len() as u64) as f64 / 100.0,
+        ..RoiReport::default()
+    };
+
+    for draw in draws {
+        let class = match draw.match_ticket(ticket, lucky).winning_class {
+            Some(class) => class,
+            None => continue,
+        };
+
+        let prize = draw
+            .winners_by_class
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .find(|class_result| class_result.class == class);
+
+        report.winning_draws += 1;
+
+        match prize {
+            Some(class_result) => report.total_winnings_chf += class_result.prize_chf,
+            None => report.unpriced_wins += 1,
+        }
+    }
+
+    Ok(report)
+}
+
+fn mask_of(numbers: &[u8]) -> u64 {
+    numbers.iter().fold(0, |mask, number| {
+        mask | 1u64.checked_shl(u32::from(*number)).unwrap_or(0)
+    })
+}
+
+/// Evaluates many tickets against `draw`, comparing number bitmasks.
+///
+/// Equivalent to calling [`LottoDraw::evaluate_ticket`] for each ticket.
+pub fn evaluate_batch(
+    draw: &LottoDraw,
+    tickets: &[([u8; 6], u8)],
+) -> Result<Vec<DrawMatch>, Errors> {
+    let draw_mask = draw.number_mask();
+
+    tickets
+        .iter()
+        .map(|(ticket, lucky)| {
+            validate_main_numbers(ticket)?;
+            validate_lucky_number(*lucky)?;
+
+            let main_matches = (draw_mask & mask_of(ticket)).count_ones() as u8;
+
+            Ok(DrawMatch::new(main_matches, draw.lucky == *lucky))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    #[test]
+    fn seven_number_system_against_matching_draw() {
+        let draw = draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 3, 1);
+
+        let matches = evaluate_system(&draw, &[1, 2, 3, 4, 5, 6, 7], 3).unwrap();
+
+        assert_eq!(matches.len(), 7);
+        assert_eq!(matches[0], DrawMatch::new(6, true));
+        assert_eq!(matches[0].winning_class, Some(1));
+        assert!(matches[1..]
+            .iter()
+            .all(|draw_match| draw_match.winning_class == Some(3)));
+    }
+
+    #[test]
+    fn six_number_system_is_a_single_ticket() {
+        let draw = draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 42], 3, 1);
+
+        let matches = evaluate_system(&draw, &[42, 1, 2, 3, 4, 41], 4).unwrap();
+
+        assert_eq!(matches, [DrawMatch::new(5, false)]);
+        assert_eq!(matches[0].winning_class, Some(4));
+    }
+
+    #[test]
+    fn system_rejects_invalid_input() {
+        let draw = draw(date(2022, 3, 30), [1, 2, 3, 4, 5, 6], 3, 1);
+
+        assert!(matches!(
+            evaluate_system(&draw, &[1, 2, 3, 4, 5, 6, 6], 3),
+            Err(Errors::DuplicateNumber(6))
+        ));
+        assert!(matches!(
+            evaluate_system(&draw, &[1, 2, 3, 4, 5], 3),
+            Err(Errors::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            evaluate_system(&draw, &[], 3),
+            Err(Errors::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            evaluate_system(&draw, &[0, 1, 2, 3, 4, 5], 3),
+            Err(Errors::InvalidNumber(0))
+        ));
+        assert!(matches!(
+            evaluate_system(&draw, &[1, 2, 3, 4, 5, 43], 3),
+            Err(Errors::InvalidNumber(43))
+        ));
+        assert!(matches!(
+            evaluate_system(&draw, &[1, 2, 3, 4, 5, 6], 0),
+            Err(Errors::InvalidLuckyNumber(0))
+        ));
+        assert!(matches!(
+            evaluate_system(&draw, &[1, 2, 3, 4, 5, 6], 7),
+            Err(Errors::InvalidLuckyNumber(7))
+        ));
+    }
+
+    #[test]
+    fn class_descriptions_match_winning_class() {
+        let expected = [
+            (6, true, Some(1), "6 + lucky number"),
+            (6, false, Some(2), "6 numbers"),
+            (5, true, Some(3), "5 + lucky number"),
+            (5, false, Some(4), "5 numbers"),
+            (4, true, Some(5), "4 + lucky number"),
+            (4, false, Some(6), "4 numbers"),
+            (3, true, Some(7), "3 + lucky number"),
+            (3, false, Some(8), "3 numbers"),
+            (2, true, None, "no win"),
+            (1, true, None, "no win"),
+            (0, false, None, "no win"),
+        ];
+
+        for (main_matches, lucky_matched, class, description) in expected {
+            let draw_match = DrawMatch::new(main_matches, lucky_matched);
+
+            assert_eq!(draw_match.winning_class, class);
+            assert_eq!(draw_match.class_description(), description);
+        }
+    }
+
+    #[test]
+    fn ticket_odds_match_published_odds() {
+        // Published as "1 in N" per class
+        let published = [
+            (1, 31_474_716.0),
+            (2, 6_294_943.0),
+            (3, 145_716.0),
+            (4, 29_143.0),
+            (5, 3_331.0),
+            (6, 666.0),
+            (7, 220.0),
+            (8, 44.0),
+        ];
+
+        let odds = ticket_odds(&[1, 2, 3, 4, 5, 6], 1).unwrap();
+
+        assert_eq!(odds.len(), 8);
+        for (class, one_in) in published {
+            assert!((1.0 / odds[&class] - one_in).abs() < 1.0, "class {}", class);
+        }
+    }
+
+    #[test]
+    fn ticket_odds_validate_ticket() {
+        assert!(matches!(
+            ticket_odds(&[1, 2, 3, 4, 5, 5], 1),
+            Err(Errors::DuplicateNumber(5))
+        ));
+        assert!(matches!(
+            ticket_odds(&[1, 2, 3, 4, 5, 6], 7),
+            Err(Errors::InvalidLuckyNumber(7))
+        ));
+        assert!(matches!(
+            ticket_odds(&[1, 2, 3, 4, 5, 6], 0),
+            Err(Errors::InvalidLuckyNumber(0))
+        ));
+        assert!(matches!(
+            ticket_odds(&[0, 2, 3, 4, 5, 6], 1),
+            Err(Errors::InvalidNumber(0))
+        ));
+        assert!(matches!(
+            ticket_odds(&[1, 2, 3, 4, 5, 43], 1),
+            Err(Errors::InvalidNumber(43))
+        ));
+        assert!(ticket_odds(&[1, 2, 3, 4, 5, 42], 6).is_ok());
+    }
+
+    #[test]
+    fn match_probability_for_zero_three_and_six_matches() {
+        let total = binomial(42, 6);
+
+        let zero = match_probability(0, false).unwrap() + match_probability(0, true).unwrap();
+        let three = match_probability(3, false).unwrap() + match_probability(3, true).unwrap();
+
+        assert!((zero - binomial(36, 6) / total).abs() < 1e-12);
+        assert!((three - 20.0 * binomial(36, 3) / total).abs() < 1e-12);
+        assert!((match_probability(6, true).unwrap() - 1.0 / (total * 6.0)).abs() < 1e-15);
+        assert!(matches!(
+            match_probability(7, false),
+            Err(Errors::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn match_probabilities_sum_to_one() {
+        let total: f64 = (0..=6)
+            .flat_map(|main_matches| [true, false].map(|lucky| (main_matches, lucky)))
+            .map(|(main_matches, lucky)| match_probability(main_matches, lucky).unwrap())
+            .sum();
+
+        assert!((total - 1.0).abs() < 1e-12);
+        assert!(matches!(
+            match_probability(u8::MAX, true),
+            Err(Errors::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn backtest_finds_single_winning_draw() {
+        let draws = [
+            draw(date(2022, 3, 23), [7, 8, 9, 10, 11, 12], 2, 1),
+            draw(date(2022, 3, 26), [1, 2, 3, 10, 11, 12], 3, 1),
+            draw(date(2022, 3, 30), [1, 2, 13, 14, 15, 16], 3, 1),
+        ];
+
+        let wins = backtest_ticket(&[1, 2, 3, 4, 5, 6], 3, &draws).unwrap();
+
+        assert_eq!(wins, [(date(2022, 3, 26), DrawMatch::new(3, true))]);
+        assert!(matches!(
+            backtest_ticket(&[1, 2, 3, 4, 5, 43], 3, &draws),
+            Err(Errors::InvalidNumber(43))
+        ));
+    }
+
+    #[test]
+    fn backtest_edge_cases() {
+        assert!(backtest_ticket(&[1, 2, 3, 4, 5, 42], 6, &[])
+            .unwrap()
+            .is_empty());
+        // the ticket is validated even without draws
+        assert!(matches!(
+            backtest_ticket(&[1, 2, 3, 4, 5, 5], 1, &[]),
+            Err(Errors::DuplicateNumber(5))
+        ));
+        assert!(matches!(
+            backtest_ticket(&[1, 2, 3, 4, 5, 42], 0, &[]),
+            Err(Errors::InvalidLuckyNumber(0))
+        ));
+
+        let jackpot = [draw(date(2022, 3, 30), [42, 5, 4, 3, 2, 1], 6, 10)];
+
+        assert_eq!(
+            backtest_ticket(&[1, 2, 3, 4, 5, 42], 6, &jackpot).unwrap(),
+            [(date(2022, 3, 30), DrawMatch::new(6, true))]
+        );
+    }
+
+    #[test]
+    fn batch_matches_single_ticket_evaluation() {
+        let draw = draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8);
+        let tickets = (1..=37)
+            .map(|first| {
+                let ticket = [first, first + 1, first + 2, first + 3, first + 4, first + 5];
+
+                (ticket, first % MAX_LUCKY_NUMBER + 1)
+            })
+            .chain([([5, 19, 28, 36, 38, 41], 5), ([5, 19, 28, 1, 2, 3], 5)])
+            .collect::<Vec<_>>();
+
+        let batch = evaluate_batch(&draw, &tickets).unwrap();
+
+        assert_eq!(batch.len(), tickets.len());
+        for ((ticket, lucky), draw_match) in tickets.iter().zip(&batch) {
+            assert_eq!(*draw_match, draw.evaluate_ticket(ticket, *lucky).unwrap());
+        }
+        assert!(matches!(
+            evaluate_batch(&draw, &[([1, 2, 3, 4, 5, 0], 1)]),
+            Err(Errors::InvalidNumber(0))
+        ));
+    }
+
+    #[test]
+    fn batch_edge_cases() {
+        let draw = draw(date(2022, 3, 30), [1, 2, 3, 40, 41, 42], 6, 8);
+
+        assert!(evaluate_batch(&draw, &[]).unwrap().is_empty());
+        assert_eq!(
+            evaluate_batch(&draw, &[([1, 2, 3, 40, 41, 42], 6)]).unwrap(),
+            [draw.evaluate_ticket(&[1, 2, 3, 40, 41, 42], 6).unwrap()]
+        );
+        assert!(matches!(
+            evaluate_batch(&draw, &[([1, 2, 3, 4, 5, 6], 7)]),
+            Err(Errors::InvalidLuckyNumber(7))
+        ));
+        assert!(matches!(
+            evaluate_batch(&draw, &[([1, 1, 3, 4, 5, 6], 1)]),
+            Err(Errors::DuplicateNumber(1))
+        ));
+    }
+
+    #[test]
+    fn number_mask_round_trip() {
+        let draw = draw(date(2022, 3, 30), [41, 5, 19, 28, 36, 38], 5, 8);
+
+        let mask = draw.number_mask();
+
+        assert_eq!(mask.count_ones(), 6);
+        assert_ne!(mask & (1 << 41), 0);
+        assert_eq!(
+            LottoDraw::numbers_from_mask(mask),
+            Some([5, 19, 28, 36, 38, 41])
+        );
+        assert_eq!(LottoDraw::numbers_from_mask(mask | (1 << 1)), None);
+        assert_eq!(LottoDraw::numbers_from_mask((1 << 43) | 0b111_1110), None);
+    }
+
+    #[test]
+    fn number_mask_edge_cases() {
+        let draw = draw(date(2022, 3, 30), [1, 2, 3, 40, 41, 42], 1, 1);
+        let mask = draw.number_mask();
+
+        assert_eq!(mask & 1, 0);
+        assert_ne!(mask & (1 << 42), 0);
+        assert_eq!(
+            LottoDraw::numbers_from_mask(mask),
+            Some([1, 2, 3, 40, 41, 42])
+        );
+        // bit 0 is not a lotto number
+        assert_eq!(LottoDraw::numbers_from_mask(mask | 1), None);
+        assert_eq!(LottoDraw::numbers_from_mask(0), None);
+        assert_eq!(LottoDraw::numbers_from_mask(0b11_1110), None);
+    }
+
+    #[test]
+    fn shared_count_matches_shared_numbers() {
+        let latest = draw(date(2022, 3, 30), [5, 19, 28, 36, 38, 41], 5, 8);
+        let others = [
+            draw(date(2022, 3, 26), [1, 2, 3, 4, 6, 7], 1, 1),
+            draw(date(2022, 3, 23), [5, 2, 3, 4, 41, 7], 1, 1),
+            draw(date(2022, 3, 19), [41, 38, 36, 28, 19, 5], 1, 1),
+        ];
+
+        for other in &others {
+            assert_eq!(
+                latest.shared_count(other) as usize,
+                latest.shared_numbers(other).len()
+            );
+        }
+        assert_eq!(latest.shared_count(&others[1]), 2);
+    }
+
+    #[test]
+    fn shared_numbers_ascending_at_boundaries() {
+        let lows_and_highs = draw(date(2022, 3, 30), [42, 1, 2, 3, 40, 41], 1, 1);
+        let other = draw(date(2022, 3, 26), [41, 20, 1, 42, 21, 22], 1, 1);
+
+        assert_eq!(lows_and_highs.shared_numbers(&other), [1, 41, 42]);
+        assert_eq!(lows_and_highs.shared_count(&other), 3);
+        assert_eq!(lows_and_highs.shared_count(&lows_and_highs), 6);
+    }
+
+    #[test]
+    fn new_and_dropped_numbers_of_overlapping_draws() {
+        let previous = draw(date(2022, 3, 26), [1, 2, 3, 4, 5, 6], 1, 1);
+        let latest = draw(date(2022, 3, 30), [9, 1, 2, 8, 7, 3], 1, 1);
+
+        assert_eq!(latest.new_numbers_vs(&previous), [7, 8, 9]);
+        assert_eq!(latest.dropped_numbers_vs(&previous), [4, 5, 6]);
+        assert!(latest.new_numbers_vs(&latest).is_empty());
+    }
+
+    #[test]
+    fn new_and_dropped_numbers_of_disjoint_draws() {
+        let previous = draw(date(2022, 3, 26), [42, 41, 40, 39, 38, 37], 1, 1);
+        let latest = draw(date(2022, 3, 30), [6, 5, 4, 3, 2, 1], 1, 1);
+
+        assert_eq!(latest.new_numbers_vs(&previous), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            latest.dropped_numbers_vs(&previous),
+            [37, 38, 39, 40, 41, 42]
+        );
+    }
+
+    #[test]
+    fn backtest_roi_with_known_prizes() {
+        let priced = |lotto_draw: LottoDraw, prizes: &[(u8, f64)]| LottoDraw {
+            winners_by_class: Some(
+                prizes
+                    .iter()
+                    .map(|(class, prize_chf)| crate::ClassResult {
+                        class: *class,
+                        winners: 1,
+                        prize_chf: *prize_chf,
+                    })
+                    .collect(),
+            ),
+            ..lotto_draw
+        };
+        let draws = [
+            // 3 numbers + lucky number: class 7
+            priced(
+                draw(date(2022, 3, 23), [1, 2, 3, 10, 11, 12], 3, 1),
+                &[(7, 21.95)],
+            ),
+            // no win
+            priced(
+                draw(date(2022, 3, 26), [7, 8, 9, 10, 11, 12], 2, 1),
+                &[(7, 20.0)],
+            ),
+            // 4 numbers: class 6
+            priced(
+                draw(date(2022, 3, 30), [1, 2, 3, 4, 11, 12], 2, 1),
+                &[(6, 77.70)],
+            ),
+            // 3 numbers without a parsed prize
+            draw(date(2022, 4, 2), [1, 2, 3, 10, 11, 12], 2, 1),
+        ];
+
+        let report = backtest_roi(&[1, 2, 3, 4, 5, 6], 3, 250, &draws).unwrap();
+
+        assert_eq!(report.draws, 4);
+        assert_eq!(report.winning_draws, 3);
+        assert_eq!(report.unpriced_wins, 1);
+        assert!((report.total_stake_chf - 10.0).abs() < 1e-9);
+        assert!((report.total_winnings_chf - 99.65).abs() < 1e-9);
+        assert!((report.net_chf() - 89.65).abs() < 1e-9);
+    }
+
+    #[test]
+    fn backtest_roi_stake_saturates() {
+        let draws = [
+            draw(date(2022, 3, 26), [7, 8, 9, 10, 11, 12], 2, 1),
+            draw(date(2022, 3, 30), [7, 8, 9, 10, 11, 12], 2, 1),
+        ];
+
+        let report = backtest_roi(&[1, 2, 3, 4, 5, 6], 3, u64::MAX, &draws).unwrap();
+
+        assert!((report.total_stake_chf - u64::MAX as f64 / 100.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn backtest_roi_of_no_draws() {
+        let report = backtest_roi(&[1, 2, 3, 4, 5, 6], 3, 250, &[]).unwrap();
+
+        assert_eq!(report, RoiReport::default());
+    }
+
+    #[test]
+    fn backtest_roi_rejects_invalid_tickets() {
+        assert!(matches!(
+            backtest_roi(&[1, 2, 3, 4, 5, 43], 3, 250, &[]),
+            Err(Errors::InvalidNumber(43))
+        ));
+        assert!(matches!(
+            backtest_roi(&[1, 2, 3, 4, 5, 6], 7, 250, &[]),
+            Err(Errors::InvalidLuckyNumber(7))
+        ));
+    }
+
+    #[test]
+    fn seven_number_system_coverage() {
+        let coverage = system_coverage(&[1, 2, 3, 4, 5, 6, 7]).unwrap();
+
+        // C(7, 6) = 7 of the C(42, 6) = 5'245'786 tickets
+        assert!((coverage - 7.0 / 5_245_786.0).abs() < 1e-15);
+        assert!((system_coverage(&[1, 2, 3, 4, 5, 6]).unwrap() - 1.0 / 5_245_786.0).abs() < 1e-15);
+
+        assert!(matches!(
+            system_coverage(&[1, 2, 3, 4, 5]),
+            Err(Errors::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            system_coverage(&[1, 2, 3, 4, 5, 6, 6]),
+            Err(Errors::DuplicateNumber(6))
+        ));
+        assert!(matches!(
+            system_coverage(&[1, 2, 3, 4, 5, 6, 43]),
+            Err(Errors::InvalidNumber(43))
+        ));
+    }
+
+    #[test]
+    fn full_system_covers_every_ticket() {
+        let all_numbers = (1..=MAX_NUMBER).collect::<Vec<_>>();
+
+        assert!((system_coverage(&all_numbers).unwrap() - 1.0).abs() < 1e-12);
+        assert!(matches!(
+            system_coverage(&[]),
+            Err(Errors::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn single_number_probabilities() {
+        assert!((single_number_draw_probability() - 1.0 / 7.0).abs() < 1e-12);
+
+        assert!(at_least_once_in(0).abs() < 1e-12);
+        assert!((at_least_once_in(1) - 1.0 / 7.0).abs() < 1e-12);
+        // 1 - (6/7)²
+        assert!((at_least_once_in(2) - 13.0 / 49.0).abs() < 1e-12);
+        assert!(at_least_once_in(1_000) > 0.999_999);
+    }
+
+    #[test]
+    fn at_least_once_in_many_draws_approaches_certainty() {
+        assert!((at_least_once_in(u32::MAX) - 1.0).abs() < 1e-12);
+        assert!(at_least_once_in(10) < at_least_once_in(11));
+    }
+}